@@ -1,39 +1,77 @@
 use std::fmt::Display;
 
 /// Dice structure
+#[derive(Clone, Debug)]
 pub struct Dice {
     faces: Vec<DiceFace>,
 }
 
 impl Dice {
-    /// Create a dice from a string of face characters (A-Z, 0-6)
-    pub fn from_string(string: &str) -> Self {
+    /// Create a dice from a list of faces
+    pub fn new(faces: Vec<DiceFace>) -> Result<Self, String> {
+        if faces.is_empty() {
+            Err("Dice has no faces")?
+        }
+
+        Ok(Dice { faces })
+    }
+
+    /// Create a dice from a string of face characters (A-Z, 0-6, ?)
+    /// where 0 is a blank face, 1 is a QU face which can be read as Q or QU, 2-6 are the
+    /// ligatures IN, TH, ER, HE and AN and ? is a wildcard face
+    pub fn from_string(string: &str) -> Result<Self, String> {
         let faces = string
             .chars()
             .map(|c| match c {
-                'A'..='Z' => DiceFace::Letter(c),
-                '0' => DiceFace::Stop,
-                '1' => DiceFace::Ligature("QU".to_string()),
-                '2' => DiceFace::Ligature("IN".to_string()),
-                '3' => DiceFace::Ligature("TH".to_string()),
-                '4' => DiceFace::Ligature("ER".to_string()),
-                '5' => DiceFace::Ligature("HE".to_string()),
-                '6' => DiceFace::Ligature("AN".to_string()),
-                _ => panic!("Unknown face value {c}"),
+                'A'..='Z' => Ok(DiceFace::Letter(c)),
+                '0' => Ok(DiceFace::Stop),
+                '1' => Ok(DiceFace::Alternatives(vec![
+                    "Q".to_string(),
+                    "QU".to_string(),
+                ])),
+                '2' => Ok(DiceFace::Ligature("IN".to_string())),
+                '3' => Ok(DiceFace::Ligature("TH".to_string())),
+                '4' => Ok(DiceFace::Ligature("ER".to_string())),
+                '5' => Ok(DiceFace::Ligature("HE".to_string())),
+                '6' => Ok(DiceFace::Ligature("AN".to_string())),
+                '?' => Ok(DiceFace::Wildcard),
+                _ => Err(format!("Unknown face value {c} in dice {string}")),
             })
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Dice { faces }
+        Self::new(faces)
     }
 
     /// Returns a face of the dice
     pub fn face(&self, elem: usize) -> DiceFace {
         self.faces[elem].clone()
     }
+
+    /// Returns the number of faces on the dice
+    pub fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns the faces of the dice
+    pub fn faces(&self) -> &[DiceFace] {
+        &self.faces
+    }
+}
+
+impl Display for Dice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let faces = self
+            .faces
+            .iter()
+            .map(|face| face.to_string())
+            .collect::<Vec<_>>();
+
+        write!(f, "[{}]", faces.join(" "))
+    }
 }
 
 /// Dice face enumeration
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DiceFace {
     /// Single letter dice face
     Letter(char),
@@ -41,18 +79,47 @@ pub enum DiceFace {
     Ligature(String),
     /// Word stop dice face
     Stop,
+    /// Wildcard dice face which can stand for any letter
+    Wildcard,
+    /// Dice face which can be read in more than one way, such as Q or QU
+    Alternatives(Vec<String>),
 }
 
 impl DiceFace {
-    /// Converts a string to a dice face
+    /// Converts a string to a dice face. A string of letters is a letter or ligature face,
+    /// an empty string is a blank face, ? is a wildcard face and readings separated by / are
+    /// an alternatives face, for example Q/QU
     pub fn from_string(string: &str) -> Result<Self, String> {
+        if string == "?" {
+            return Ok(DiceFace::Wildcard);
+        }
+
+        if string.contains('/') {
+            let readings = string
+                .split('/')
+                .map(|reading| match Self::from_string(reading)? {
+                    DiceFace::Letter(c) => Ok(c.to_string()),
+                    DiceFace::Ligature(s) => Ok(s),
+                    _ => Err(format!("Invalid dice face: {string}")),
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            return Ok(DiceFace::Alternatives(readings));
+        }
+
         let chars = string
             .chars()
             .map(|c| {
-                if !c.is_ascii_alphabetic() {
+                if !c.is_alphabetic() {
                     None
                 } else {
-                    Some(c.to_ascii_uppercase())
+                    // Only use the upper case form if it is a single character
+                    let mut upper = c.to_uppercase();
+
+                    match (upper.next(), upper.next()) {
+                        (Some(u), None) => Some(u),
+                        _ => Some(c),
+                    }
                 }
             })
             .collect::<Option<Vec<_>>>();
@@ -69,6 +136,19 @@ impl DiceFace {
             Err(format!("Invalid dice face: {string}"))
         }
     }
+
+    /// Determines if a board face could be this dice face showing. Alternatives faces also
+    /// match a letter or ligature face of one of their readings, so a QU typed from a
+    /// physical board matches a Q/QU dice face
+    pub fn matches(&self, face: &DiceFace) -> bool {
+        match (self, face) {
+            (DiceFace::Alternatives(readings), DiceFace::Letter(c)) => {
+                readings.iter().any(|r| r.chars().eq(std::iter::once(*c)))
+            }
+            (DiceFace::Alternatives(readings), DiceFace::Ligature(s)) => readings.contains(s),
+            _ => self == face,
+        }
+    }
 }
 
 impl Display for DiceFace {
@@ -79,6 +159,8 @@ impl Display for DiceFace {
             Self::Letter(c) => write!(f, "{:<width$}", *c),
             Self::Ligature(s) => write!(f, "{:<width$}", s),
             Self::Stop => write!(f, "{:<width$}", "█"),
+            Self::Wildcard => write!(f, "{:<width$}", "?"),
+            Self::Alternatives(readings) => write!(f, "{:<width$}", readings.join("/")),
         }
     }
 }
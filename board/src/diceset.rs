@@ -0,0 +1,315 @@
+use std::fs::read_to_string;
+use std::path::Path;
+use std::sync::Arc;
+
+use dictionary::Alphabet;
+use serde::Deserialize;
+
+use crate::dice::{Dice, DiceFace};
+
+/// Set of dice for a game, typically loaded from a TOML or JSON file
+///
+/// ```toml
+/// name = "House rules"
+/// width = 4
+/// height = 4
+/// depth = 1                # Optional, number of layers for 3D boards
+/// faces = 6                # Optional, checks every dice has this number of faces
+/// alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZ" # Optional, defaults to English
+/// dice = [
+///     "AACIOT",                         # One letter per face
+///     ["A", "B", "J", "M", "O", "QU"],  # One string per face, "" is a blank face, "?" a wildcard
+///     ...
+/// ]
+/// ```
+#[derive(Clone, Debug)]
+pub struct DiceSet {
+    name: String,
+    width: usize,
+    height: usize,
+    depth: usize,
+    dice: Vec<Dice>,
+    alphabet: Arc<Alphabet>,
+}
+
+/// Dice set file contents
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DiceSetFile {
+    name: String,
+    width: usize,
+    height: usize,
+    depth: Option<usize>,
+    faces: Option<usize>,
+    alphabet: Option<String>,
+    dice: Vec<DiceFile>,
+}
+
+/// Dice definition in a dice set file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DiceFile {
+    /// String of single letter faces
+    Letters(String),
+    /// List of faces
+    Faces(Vec<String>),
+}
+
+impl DiceSet {
+    /// Creates a new dice set. There must be at least one dice for each board position and
+    /// all dice letters must be in the alphabet
+    pub fn new(
+        name: &str,
+        width: usize,
+        height: usize,
+        dice: Vec<Dice>,
+        alphabet: Alphabet,
+    ) -> Result<Self, String> {
+        Self::new_with_depth(name, width, height, 1, dice, alphabet)
+    }
+
+    /// Creates a new dice set for a 3D board with a number of layers
+    pub fn new_with_depth(
+        name: &str,
+        width: usize,
+        height: usize,
+        depth: usize,
+        dice: Vec<Dice>,
+        alphabet: Alphabet,
+    ) -> Result<Self, String> {
+        let size = if depth == 1 {
+            format!("{width}x{height}")
+        } else {
+            format!("{width}x{height}x{depth}")
+        };
+
+        if width == 0 || height == 0 || depth == 0 {
+            Err(format!("Dice set {name}: board size {size} is invalid"))?
+        }
+
+        if dice.len() < width * height * depth {
+            Err(format!(
+                "Dice set {name}: board size {size} needs {} dice, {} given",
+                width * height * depth,
+                dice.len()
+            ))?
+        }
+
+        for face in dice.iter().flat_map(|d| d.faces()) {
+            let letters = match face {
+                DiceFace::Letter(c) => vec![*c],
+                DiceFace::Ligature(s) => s.chars().collect(),
+                DiceFace::Stop | DiceFace::Wildcard => vec![],
+                DiceFace::Alternatives(readings) => {
+                    readings.iter().flat_map(|r| r.chars()).collect()
+                }
+            };
+
+            if let Some(c) = letters
+                .iter()
+                .find(|c| alphabet.char_to_elem(**c).is_none())
+            {
+                Err(format!(
+                    "Dice set {name}: letter {c} is not in the alphabet {alphabet}"
+                ))?
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            width,
+            height,
+            depth,
+            dice,
+            alphabet: Arc::new(alphabet),
+        })
+    }
+
+    /// Loads a dice set from a file. Files with a .json extension are loaded as JSON,
+    /// otherwise the file is loaded as TOML
+    pub fn from_file(file: &str) -> Result<Self, String> {
+        let contents = read_to_string(file)
+            .map_err(|e| format!("Unable to read dice set file {file}: {e}"))?;
+
+        let is_json = Path::new(file)
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        }
+        .map_err(|e| format!("{file}: {e}"))
+    }
+
+    /// Loads a dice set from a TOML string
+    pub fn from_toml(string: &str) -> Result<Self, String> {
+        Self::from_set_file(toml::from_str(string).map_err(|e| e.to_string())?)
+    }
+
+    /// Loads a dice set from a JSON string
+    pub fn from_json(string: &str) -> Result<Self, String> {
+        Self::from_set_file(serde_json::from_str(string).map_err(|e| e.to_string())?)
+    }
+
+    /// Converts the dice set file contents to a dice set
+    fn from_set_file(file: DiceSetFile) -> Result<Self, String> {
+        let alphabet = match &file.alphabet {
+            Some(letters) => Alphabet::new(letters)?,
+            None => Alphabet::english(),
+        };
+
+        let dice = file
+            .dice
+            .iter()
+            .enumerate()
+            .map(|(i, dice)| {
+                let faces = match dice {
+                    DiceFile::Letters(s) => s
+                        .chars()
+                        .map(|c| DiceFace::from_string(&c.to_string()))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    DiceFile::Faces(faces) => faces
+                        .iter()
+                        .map(|f| DiceFace::from_string(f))
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+
+                if let Some(count) = file.faces {
+                    if faces.len() != count {
+                        Err(format!(
+                            "Dice {} has {} faces, expected {count}",
+                            i + 1,
+                            faces.len()
+                        ))?
+                    }
+                }
+
+                Dice::new(faces).map_err(|e| format!("Dice {}: {e}", i + 1))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(|e| format!("Dice set {}: {e}", file.name))?;
+
+        Self::new_with_depth(
+            &file.name,
+            file.width,
+            file.height,
+            file.depth.unwrap_or(1),
+            dice,
+            alphabet,
+        )
+    }
+
+    /// Returns the name of the dice set
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the dimensions of the Boggle board for the dice set
+    pub fn layout(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the number of layers of the Boggle board for the dice set. This is 1 except
+    /// for 3D boards
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the dice in the set
+    pub fn dice(&self) -> &[Dice] {
+        &self.dice
+    }
+
+    /// Returns the alphabet used by the dice
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// Returns the alphabet used by the dice, shared with the boards rolled from them
+    pub(crate) fn shared_alphabet(&self) -> &Arc<Alphabet> {
+        &self.alphabet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml() {
+        let set = DiceSet::from_toml(
+            r#"
+            name = "Tiny"
+            width = 2
+            height = 2
+            faces = 3
+            dice = [
+                "ABC",
+                ["D", "QU", ""],
+                ["e", "f", "Th"],
+                "XYZ",
+                "JKL",
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(set.name(), "Tiny");
+        assert_eq!(set.layout(), (2, 2));
+        assert_eq!(set.dice().len(), 5);
+        assert_eq!(set.dice()[0].face(2), DiceFace::Letter('C'));
+        assert_eq!(
+            set.dice()[1].faces(),
+            &[
+                DiceFace::Letter('D'),
+                DiceFace::Ligature("QU".to_string()),
+                DiceFace::Stop
+            ]
+        );
+        assert_eq!(set.dice()[2].face(2), DiceFace::Ligature("TH".to_string()));
+    }
+
+    #[test]
+    fn json() {
+        let set = DiceSet::from_json(
+            r#"{
+                "name": "Nordic",
+                "width": 1,
+                "height": 2,
+                "alphabet": "ABCÆØÅ",
+                "dice": ["ÆØÅ", ["A", "B", "C", "ÆØ"]]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(set.alphabet().len(), 6);
+        assert_eq!(set.dice()[0].face(1), DiceFace::Letter('Ø'));
+        assert_eq!(set.dice()[1].face_count(), 4);
+    }
+
+    #[test]
+    fn invalid() {
+        let set = |dice: &str, extra: &str| {
+            DiceSet::from_toml(&format!(
+                "name = \"Bad\"\nwidth = 1\nheight = 2\n{extra}\ndice = {dice}"
+            ))
+        };
+
+        assert!(set(r#"["ABC", "DEF"]"#, "").is_ok());
+
+        // Not enough dice
+        assert!(set(r#"["ABC"]"#, "").is_err());
+        // Wrong number of faces
+        assert!(set(r#"["ABC", "DEFG"]"#, "faces = 3").is_err());
+        // Dice with no faces
+        assert!(set(r#"["ABC", []]"#, "").is_err());
+        // Invalid face
+        assert!(set(r#"["ABC", ["D", "E1"]]"#, "").is_err());
+        // Letter not in the alphabet
+        assert!(set(r#"["ABC", "DEÄ"]"#, "").is_err());
+        // Unknown field
+        assert!(set(r#"["ABC", "DEF"]"#, "colour = \"red\"").is_err());
+    }
+}
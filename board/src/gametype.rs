@@ -8,9 +8,15 @@
 // boggleDice_Super_Big = ['AAAFRS', 'AAEEEE', 'AAEEOO', 'AAFIRS', 'ABDEIO', 'ADENNN', 'AEEEEM', 'AEEGMU', 'AEGMNN', 'AEILMN', 'AEINOU', 'AFIRSY', '123456', 'BBJKXZ', 'CCENST', 'CDDLNN', 'CEIITT', 'CEIPST', 'CFGNUY', 'DDHNOT', 'DHHLOR', 'DHHNOW', 'DHLNOR', 'EHILRS', 'EIILST', 'EILPST', 'EIO000', 'EMTTTO', 'ENSSSU', 'GORRVW', 'HIRSTV', 'HOPRST', 'IPRSYY', 'JK1WXZ', 'NOOTUW', 'OOOTTU']
 // #0 = Blank, 1 = Qu, 2 = In, 3 = Th, 4 = Er, 5 = He, 6 = An
 
+use std::sync::{Arc, OnceLock};
+
+use dictionary::Alphabet;
+
 use crate::dice::Dice;
+use crate::diceset::DiceSet;
 
 /// Game type enumeration
+#[derive(Clone, Debug)]
 pub enum GameType {
     /// Original 4x4 English Boggle
     Classic,
@@ -26,9 +32,57 @@ pub enum GameType {
     Big2012,
     /// 6x6 English Boggle
     SuperBig,
+    /// Custom dice set
+    Custom(DiceSet),
 }
 
+/// Names of the built in game types
+const NAMES: [&str; 7] = [
+    "classic",
+    "new",
+    "big-original",
+    "big-challenge",
+    "big-deluxe",
+    "big2012",
+    "super-big",
+];
+
 impl GameType {
+    /// Returns a built in game type from its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let game_type = match name.to_lowercase().as_str() {
+            "classic" => GameType::Classic,
+            "new" => GameType::New,
+            "big-original" => GameType::BigOriginal,
+            "big-challenge" => GameType::BigChallenge,
+            "big-deluxe" => GameType::BigDeluxe,
+            "big2012" => GameType::Big2012,
+            "super-big" => GameType::SuperBig,
+            _ => return None,
+        };
+
+        Some(game_type)
+    }
+
+    /// Returns the names of the built in game types
+    pub fn names() -> &'static [&'static str] {
+        &NAMES
+    }
+
+    /// Returns the name of the game type
+    pub fn name(&self) -> &str {
+        match self {
+            GameType::Classic => NAMES[0],
+            GameType::New => NAMES[1],
+            GameType::BigOriginal => NAMES[2],
+            GameType::BigChallenge => NAMES[3],
+            GameType::BigDeluxe => NAMES[4],
+            GameType::Big2012 => NAMES[5],
+            GameType::SuperBig => NAMES[6],
+            GameType::Custom(set) => set.name(),
+        }
+    }
+
     /// Returns the dice used in a particular game variant
     pub fn dice(&self) -> Vec<Dice> {
         let faces = match self {
@@ -71,9 +125,25 @@ impl GameType {
                 "EIILST", "EILPST", "EIO000", "EMTTTO", "ENSSSU", "GORRVW", "HIRSTV", "HOPRST",
                 "IPRSYY", "JK1WXZ", "NOOTUW", "OOOTTU",
             ],
+            GameType::Custom(set) => return set.dice().to_vec(),
         };
 
-        faces.iter().map(|f| Dice::from_string(f)).collect()
+        faces
+            .iter()
+            .map(|f| Dice::from_string(f).expect("Built in dice should be valid"))
+            .collect()
+    }
+
+    /// Returns the alphabet used by the dice of a game variant, shared by every board rolled
+    pub fn alphabet(&self) -> Arc<Alphabet> {
+        static ENGLISH: OnceLock<Arc<Alphabet>> = OnceLock::new();
+
+        match self {
+            GameType::Custom(set) => set.shared_alphabet().clone(),
+            _ => ENGLISH
+                .get_or_init(|| Arc::new(Alphabet::english()))
+                .clone(),
+        }
     }
 
     /// Returns the dimensions of the Boggle board for a variant
@@ -85,6 +155,16 @@ impl GameType {
             | GameType::BigDeluxe
             | GameType::Big2012 => (5, 5),
             GameType::SuperBig => (6, 6),
+            GameType::Custom(set) => set.layout(),
+        }
+    }
+
+    /// Returns the number of layers of the Boggle board for a variant. This is 1 except for
+    /// custom 3D dice sets
+    pub fn depth(&self) -> usize {
+        match self {
+            GameType::Custom(set) => set.depth(),
+            _ => 1,
         }
     }
 }
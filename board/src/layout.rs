@@ -0,0 +1,362 @@
+use std::fs::read_to_string;
+use std::path::Path;
+use std::sync::Arc;
+
+use dictionary::Alphabet;
+use serde::Deserialize;
+
+use crate::{Board, DiceFace, Topology};
+
+/// Largest layout width and height, the same as the largest board given with dimensions
+const MAX_SIZE: usize = u8::MAX as usize;
+
+/// Layout file in JSON format
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutFile {
+    topology: Option<String>,
+    cells: Vec<LayoutCell>,
+    #[serde(default)]
+    links: Vec<(usize, usize)>,
+}
+
+/// Cell definition in a JSON layout file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutCell {
+    x: usize,
+    y: usize,
+    face: String,
+}
+
+impl Board {
+    /// Loads a shaped board from a layout file. Files with a .json extension are loaded as
+    /// JSON, otherwise the file is loaded as text
+    pub fn from_layout_file(file: &str, alphabet: &Alphabet) -> Result<Self, String> {
+        let contents =
+            read_to_string(file).map_err(|e| format!("Unable to read layout file {file}: {e}"))?;
+
+        let is_json = Path::new(file)
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            Self::from_layout_json(&contents, alphabet)
+        } else {
+            Self::from_layout_text(&contents, alphabet)
+        }
+        .map_err(|e| format!("{file}: {e}"))
+    }
+
+    /// Loads a shaped board from a text layout. Each line is a row of dice faces separated by
+    /// spaces, with `.` marking an empty position. Cells are adjacent as on a square board,
+    /// and lines of the form `link x1,y1 x2,y2` add a link between two cells. Blank lines and
+    /// lines starting with `#` are ignored
+    ///
+    /// ```text
+    /// # Plus shaped board with the top and bottom cells linked
+    /// .  A  B  .
+    /// C  D  E  F
+    /// G  H  I  J
+    /// .  K  QU .
+    /// link 1,0 1,3
+    /// ```
+    pub fn from_layout_text(string: &str, alphabet: &Alphabet) -> Result<Self, String> {
+        let mut faces = Vec::new();
+        let mut positions = Vec::new();
+        let mut link_positions = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (line_no, line) in string.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |e: String| format!("Line {}: {e}", line_no + 1);
+
+            let mut tokens = line.split_whitespace();
+
+            if line.starts_with("link ") {
+                tokens.next();
+
+                let mut pos = || {
+                    tokens
+                        .next()
+                        .and_then(parse_pos)
+                        .ok_or_else(|| error("Expected link x1,y1 x2,y2".into()))
+                };
+
+                link_positions.push((pos()?, pos()?, line_no + 1));
+                continue;
+            }
+
+            let mut x = 0;
+
+            for token in tokens {
+                if token != "." {
+                    faces.push(DiceFace::from_string(token).map_err(error)?);
+                    positions.push((x, height));
+                }
+
+                x += 1;
+            }
+
+            width = width.max(x);
+            height += 1;
+        }
+
+        // Convert link positions to cell numbers
+        let cell = |pos: (usize, usize), line_no: usize| {
+            positions
+                .iter()
+                .position(|p| *p == pos)
+                .ok_or_else(|| format!("Line {line_no}: No cell at position ({},{})", pos.0, pos.1))
+        };
+
+        let links = link_positions
+            .into_iter()
+            .map(|(a, b, line_no)| Ok((cell(a, line_no)?, cell(b, line_no)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Self::from_layout(
+            width,
+            height,
+            faces,
+            positions,
+            links,
+            Topology::Square,
+            alphabet,
+        )
+    }
+
+    /// Loads a board from a JSON layout. Each cell has a grid position for display and a dice
+    /// face, and links join pairs of cells by their index in the list. Cells are only adjacent
+    /// where linked unless a grid topology is given
+    ///
+    /// ```json
+    /// {
+    ///     "topology": "square",
+    ///     "cells": [
+    ///         { "x": 0, "y": 0, "face": "A" },
+    ///         { "x": 1, "y": 0, "face": "QU" },
+    ///         { "x": 2, "y": 0, "face": "B" }
+    ///     ],
+    ///     "links": [[0, 2]]
+    /// }
+    /// ```
+    pub fn from_layout_json(string: &str, alphabet: &Alphabet) -> Result<Self, String> {
+        let file: LayoutFile = serde_json::from_str(string).map_err(|e| e.to_string())?;
+
+        let topology = match &file.topology {
+            Some(name) => Topology::from_name(name).ok_or_else(|| {
+                format!(
+                    "Unknown topology {name} (expected one of {})",
+                    Topology::NAMES.join(", ")
+                )
+            })?,
+            None => Topology::Graph,
+        };
+
+        if let Some(c) = file
+            .cells
+            .iter()
+            .find(|c| c.x >= MAX_SIZE || c.y >= MAX_SIZE)
+        {
+            Err(format!(
+                "Cell position ({},{}) is outside the largest layout of {MAX_SIZE}x{MAX_SIZE}",
+                c.x, c.y
+            ))?
+        }
+
+        let width = file.cells.iter().map(|c| c.x + 1).max().unwrap_or(0);
+        let height = file.cells.iter().map(|c| c.y + 1).max().unwrap_or(0);
+
+        let faces = file
+            .cells
+            .iter()
+            .map(|c| DiceFace::from_string(&c.face))
+            .collect::<Result<Vec<_>, String>>()?;
+        let positions = file.cells.iter().map(|c| (c.x, c.y)).collect();
+
+        Self::from_layout(
+            width, height, faces, positions, file.links, topology, alphabet,
+        )
+    }
+
+    /// Creates a shaped board, checking it has at least one cell
+    fn from_layout(
+        width: usize,
+        height: usize,
+        faces: Vec<DiceFace>,
+        positions: Vec<(usize, usize)>,
+        links: Vec<(usize, usize)>,
+        topology: Topology,
+        alphabet: &Alphabet,
+    ) -> Result<Self, String> {
+        if faces.is_empty() {
+            Err("Layout has no cells")?
+        }
+
+        if width > MAX_SIZE || height > MAX_SIZE {
+            Err(format!(
+                "Layout is {width}x{height}, larger than the largest layout of {MAX_SIZE}x{MAX_SIZE}"
+            ))?
+        }
+
+        let positions = positions.into_iter().map(|(x, y)| (x, y, 0)).collect();
+
+        Self::from_cells(
+            width,
+            height,
+            1,
+            faces,
+            positions,
+            links,
+            topology,
+            Arc::new(alphabet.clone()),
+        )
+    }
+}
+
+/// Parses an x,y position
+fn parse_pos(string: &str) -> Option<(usize, usize)> {
+    let (x, y) = string.split_once(',')?;
+
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbours(board: &Board, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut n = board.neighbours(x, y);
+        n.sort();
+        n
+    }
+
+    #[test]
+    fn text() {
+        let board = Board::from_layout_text(
+            "
+            # Plus shaped
+            .  A  B  .
+            C  D  E  F
+            G  H  I  J
+            .  K  QU .
+            link 1,0 1,3
+            ",
+            &Alphabet::english(),
+        )
+        .unwrap();
+
+        assert_eq!(board.dimension(), (4, 4));
+        assert_eq!(board.cell_count(), 12);
+        assert!(!board.is_rectangular());
+        assert_eq!(board.cell_at(0, 0), None);
+        assert_eq!(board.face(2, 3), DiceFace::Ligature("QU".into()));
+
+        // Holes are skipped and the link is added in both directions
+        assert_eq!(
+            neighbours(&board, 1, 0),
+            vec![(0, 1), (1, 1), (1, 3), (2, 0), (2, 1)]
+        );
+        assert_eq!(
+            neighbours(&board, 1, 3),
+            vec![(0, 2), (1, 0), (1, 2), (2, 2), (2, 3)]
+        );
+
+        // Topology still applies to the grid positions
+        let board = board.with_topology(Topology::Toroidal);
+        assert!(neighbours(&board, 0, 1).contains(&(3, 1)));
+    }
+
+    #[test]
+    fn json() {
+        // Ring of 4 cells
+        let board = Board::from_layout_json(
+            r#"{
+                "cells": [
+                    { "x": 1, "y": 0, "face": "A" },
+                    { "x": 2, "y": 1, "face": "B" },
+                    { "x": 1, "y": 2, "face": "C" },
+                    { "x": 0, "y": 1, "face": "D" }
+                ],
+                "links": [[0, 1], [1, 2], [2, 3], [3, 0]]
+            }"#,
+            &Alphabet::english(),
+        )
+        .unwrap();
+
+        assert_eq!(board.dimension(), (3, 3));
+        assert_eq!(board.topology(), Topology::Graph);
+        assert_eq!(neighbours(&board, 1, 0), vec![(0, 1), (2, 1)]);
+        assert_eq!(neighbours(&board, 2, 1), vec![(1, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn invalid() {
+        let alphabet = Alphabet::english();
+
+        let err = Board::from_layout_text("A B\nlink 0,0 2,0", &alphabet)
+            .err()
+            .unwrap();
+        assert_eq!(err, "Line 2: No cell at position (2,0)");
+
+        let err = Board::from_layout_text("A B\nlink 0,0", &alphabet)
+            .err()
+            .unwrap();
+        assert_eq!(err, "Line 2: Expected link x1,y1 x2,y2");
+
+        assert_eq!(
+            Board::from_layout_text("# Nothing\n. .", &alphabet)
+                .err()
+                .unwrap(),
+            "Layout has no cells"
+        );
+
+        let err = Board::from_layout_json(
+            r#"{ "cells": [{ "x": 0, "y": 0, "face": "A" }], "links": [[0, 1]] }"#,
+            &alphabet,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err, "Invalid link between cells 0 and 1");
+
+        let err = Board::from_layout_json(
+            r#"{ "cells": [{ "x": 0, "y": 0, "face": "A" }, { "x": 0, "y": 0, "face": "B" }] }"#,
+            &alphabet,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err, "More than one cell at position (0,0)");
+
+        let err = Board::from_layout_json(
+            &format!(
+                r#"{{ "cells": [{{ "x": {}, "y": 0, "face": "A" }}] }}"#,
+                usize::MAX
+            ),
+            &alphabet,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err,
+            format!(
+                "Cell position ({},0) is outside the largest layout of 255x255",
+                usize::MAX
+            )
+        );
+
+        let err = Board::from_layout_text(&"A ".repeat(256), &alphabet)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            "Layout is 256x1, larger than the largest layout of 255x255"
+        );
+    }
+}
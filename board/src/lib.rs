@@ -2,21 +2,48 @@
 
 //! Boggle board structures
 
-use dictionary::Dictionary;
-use rand::Rng;
+use std::sync::Arc;
+
+use dictionary::Alphabet;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 mod dice;
+mod diceset;
 mod gametype;
-
-pub use dice::DiceFace;
+mod layout;
+mod possible;
+mod probability;
+mod scoring;
+mod topology;
+
+pub use dice::{Dice, DiceFace};
+pub use diceset::DiceSet;
 pub use gametype::GameType;
-
-/// Boggle board
+pub use probability::FaceProbability;
+pub use scoring::{CellBonus, LetterValues};
+pub use topology::Topology;
+
+/// Boggle board. Cells are usually laid out in a rectangular grid, but shaped boards loaded
+/// from a layout file may leave grid positions empty and add links between any two cells.
+/// 3D boards stack more than one layer of the grid
+#[derive(Clone)]
 pub struct Board {
     x: usize,
     y: usize,
-    faces: Vec<Vec<DiceFace>>,
-    dict_ents: Vec<Vec<Vec<u8>>>,
+    z: usize,
+    faces: Vec<DiceFace>,
+    positions: Vec<(usize, usize, usize)>,
+    grid: Vec<Option<usize>>,
+    links: Vec<(usize, usize)>,
+    alphabet: Arc<Alphabet>,
+    topology: Topology,
+    dict_ents: Vec<Vec<u8>>,
+    readings: Vec<Vec<Vec<u8>>>,
+    neighbours: Vec<Vec<usize>>,
+    letter_values: Option<LetterValues>,
+    elem_values: Vec<u32>,
+    bonuses: Vec<CellBonus>,
 }
 
 impl Board {
@@ -25,9 +52,42 @@ impl Board {
         width: Option<u8>,
         height: Option<u8>,
         dice_faces: Vec<String>,
+    ) -> Result<Self, String> {
+        Self::new_with_alphabet(width, height, dice_faces, &Alphabet::english())
+    }
+
+    /// Creates a new board from given optional dimensions and dice faces using an alphabet
+    pub fn new_with_alphabet(
+        width: Option<u8>,
+        height: Option<u8>,
+        dice_faces: Vec<String>,
+        alphabet: &Alphabet,
+    ) -> Result<Self, String> {
+        Self::new_with_depth(width, height, 1, dice_faces, alphabet)
+    }
+
+    /// Creates a new 3D board with a number of layers from given optional layer dimensions and
+    /// dice faces using an alphabet. The dice faces fill the first layer row by row, then the
+    /// second layer and so on
+    pub fn new_with_depth(
+        width: Option<u8>,
+        height: Option<u8>,
+        depth: u8,
+        dice_faces: Vec<String>,
+        alphabet: &Alphabet,
     ) -> Result<Self, String> {
         let x;
         let y;
+        let z = depth as usize;
+
+        if z == 0 || !dice_faces.len().is_multiple_of(z) {
+            Err(format!(
+                "Unable to split {} dice faces into {z} layers",
+                dice_faces.len()
+            ))?
+        }
+
+        let layer_faces = dice_faces.len() / z;
 
         // Calculate board dimensions
         if let Some(width) = width {
@@ -36,7 +96,7 @@ impl Board {
             if let Some(height) = height {
                 y = height as usize;
             } else {
-                let calc_y = dice_faces.len() as f32 / x as f32;
+                let calc_y = layer_faces as f32 / x as f32;
 
                 if calc_y.fract() == 0.0 {
                     y = calc_y as usize;
@@ -48,7 +108,7 @@ impl Board {
         } else if let Some(height) = height {
             y = height as usize;
 
-            let calc_x = dice_faces.len() as f32 / y as f32;
+            let calc_x = layer_faces as f32 / y as f32;
 
             if calc_x.fract() == 0.0 {
                 x = calc_x as usize;
@@ -57,7 +117,7 @@ impl Board {
                 Err("Unable to calculate board width from height and number of dice faces")?
             }
         } else {
-            let calc_dim = (dice_faces.len() as f32).sqrt();
+            let calc_dim = (layer_faces as f32).sqrt();
 
             if calc_dim.fract() == 0.0 {
                 x = calc_dim as usize;
@@ -69,86 +129,279 @@ impl Board {
             }
         }
 
+        if x * y * z != dice_faces.len() {
+            Err(format!(
+                "Board size {} needs {} dice faces, {} given",
+                size_string(x, y, z),
+                x * y * z,
+                dice_faces.len()
+            ))?
+        }
+
         // Get flat vector of dice faces
-        let faces_flat = dice_faces
+        let faces = dice_faces
             .iter()
             .map(|f| DiceFace::from_string(f))
             .collect::<Result<Vec<_>, String>>()?;
 
-        // Build 2-d vector of dice faces
-        let faces = faces_flat
-            .chunks(x)
-            .map(|row| row.to_vec())
-            .collect::<Vec<_>>();
+        Self::from_faces(x, y, z, faces, Arc::new(alphabet.clone()))
+    }
 
-        // Build dictionary elements vector
-        let dict_ents = Self::build_dict_ents(&faces);
+    /// Creates a new board of given dimensions from dice faces using a shared alphabet. The dice
+    /// faces fill the first layer row by row, then the second layer and so on
+    pub fn new_from_faces(
+        width: usize,
+        height: usize,
+        depth: usize,
+        faces: Vec<DiceFace>,
+        alphabet: &Arc<Alphabet>,
+    ) -> Result<Self, String> {
+        if width * height * depth != faces.len() {
+            Err(format!(
+                "Board size {} needs {} dice faces, {} given",
+                size_string(width, height, depth),
+                width * height * depth,
+                faces.len()
+            ))?
+        }
 
-        Ok(Self {
-            x,
-            y,
-            faces,
-            dict_ents,
-        })
+        Self::from_faces(width, height, depth, faces, alphabet.clone())
     }
 
     /// Creates a new random Boggle board for the given game type
     pub fn new_random(game_type: GameType) -> Board {
+        Self::new_random_with_rng(game_type, &mut rand::thread_rng())
+    }
+
+    /// Creates a new random Boggle board for the given game type from a seed.
+    /// The same seed always produces the same board, including across rand releases
+    pub fn new_random_seeded(game_type: GameType, seed: u64) -> Board {
+        Self::new_random_with_rng(game_type, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Creates a new random Boggle board for the given game type using a random number generator
+    pub fn new_random_with_rng<R>(game_type: GameType, rng: &mut R) -> Board
+    where
+        R: Rng + ?Sized,
+    {
         let (x, y) = game_type.layout();
+        let z = game_type.depth();
         let mut dice = game_type.dice();
+        let alphabet = game_type.alphabet();
 
-        let mut faces = Vec::with_capacity(y);
+        let mut faces = Vec::with_capacity(x * y * z);
 
-        let mut rng = rand::thread_rng();
+        for _ in 0..x * y * z {
+            // Choose a dice
+            let dice_elem = rng.gen_range(0..dice.len());
+            let dice = dice.swap_remove(dice_elem);
 
-        for _ in 0..y {
-            let mut faces_row = Vec::with_capacity(x);
+            // Choose a face
+            let face_elem = rng.gen_range(0..dice.face_count());
+            let face = dice.face(face_elem);
 
-            for _ in 0..x {
-                // Choose a dice
-                let dice_elem = rng.gen_range(0..dice.len());
-                let dice = dice.swap_remove(dice_elem);
+            // Add to faces
+            faces.push(face);
+        }
 
-                // Choose a face
-                let face_elem = rng.gen_range(0..6);
-                let face = dice.face(face_elem);
+        Self::from_faces(x, y, z, faces, alphabet)
+            .expect("Dice letters should be in the game type alphabet")
+    }
 
-                // Add to faces
-                faces_row.push(face);
+    /// Creates a board from a flat vector of dice faces
+    fn from_faces(
+        x: usize,
+        y: usize,
+        z: usize,
+        faces: Vec<DiceFace>,
+        alphabet: Arc<Alphabet>,
+    ) -> Result<Self, String> {
+        let positions = (0..z)
+            .flat_map(|z| (0..y).flat_map(move |y| (0..x).map(move |x| (x, y, z))))
+            .collect();
+
+        Self::from_cells(
+            x,
+            y,
+            z,
+            faces,
+            positions,
+            vec![],
+            Topology::default(),
+            alphabet,
+        )
+    }
+
+    /// Creates a board from dice faces at given (x, y, z) positions of a grid, with extra
+    /// links between pairs of cells
+    #[allow(clippy::too_many_arguments)]
+    fn from_cells(
+        x: usize,
+        y: usize,
+        z: usize,
+        faces: Vec<DiceFace>,
+        positions: Vec<(usize, usize, usize)>,
+        links: Vec<(usize, usize)>,
+        topology: Topology,
+        alphabet: Arc<Alphabet>,
+    ) -> Result<Self, String> {
+        // Build position to cell lookup
+        let mut grid = vec![None; x * y * z];
+
+        for (cell, &(px, py, pz)) in positions.iter().enumerate() {
+            if px >= x || py >= y || pz >= z {
+                Err(format!(
+                    "Cell position {} is outside the {} board",
+                    pos_string(px, py, pz, z),
+                    size_string(x, y, z)
+                ))?
             }
 
-            faces.push(faces_row);
+            if grid[(((pz * y) + py) * x) + px].replace(cell).is_some() {
+                Err(format!(
+                    "More than one cell at position {}",
+                    pos_string(px, py, pz, z)
+                ))?
+            }
         }
 
-        // Build dictionary elements vector
-        let dict_ents = Self::build_dict_ents(&faces);
+        if let Some((a, b)) = links
+            .iter()
+            .find(|(a, b)| *a >= faces.len() || *b >= faces.len() || a == b)
+        {
+            Err(format!("Invalid link between cells {a} and {b}"))?
+        }
 
-        Board {
+        // Build dictionary elements vectors
+        let dict_ents = Self::build_dict_ents(&faces, &alphabet)?;
+        let readings = Self::build_readings(&faces, &alphabet)?;
+        let faces_len = faces.len();
+
+        let mut board = Self {
             x,
             y,
+            z,
             faces,
+            positions,
+            grid,
+            links,
+            alphabet,
+            topology,
             dict_ents,
+            readings,
+            neighbours: vec![],
+            letter_values: None,
+            elem_values: vec![],
+            bonuses: vec![CellBonus::None; faces_len],
+        };
+
+        // Build neighbours table
+        board.neighbours = board.build_neighbours();
+
+        Ok(board)
+    }
+
+    /// Converts the board to use a different alphabet, for example to match a dictionary
+    pub fn with_alphabet(mut self, alphabet: &Alphabet) -> Result<Self, String> {
+        if *self.alphabet != *alphabet {
+            self.dict_ents = Self::build_dict_ents(&self.faces, alphabet)?;
+            self.readings = Self::build_readings(&self.faces, alphabet)?;
+            self.alphabet = Arc::new(alphabet.clone());
+            self.elem_values = self.build_elem_values();
+        }
+
+        Ok(self)
+    }
+
+    /// Replaces every occurrence of a dice face on the board with another face, for example to
+    /// read QU faces as Q or QU
+    pub fn with_face_replaced(mut self, from: &DiceFace, to: &DiceFace) -> Result<Self, String> {
+        for face in self.faces.iter_mut().filter(|f| *f == from) {
+            *face = to.clone();
         }
+
+        self.dict_ents = Self::build_dict_ents(&self.faces, &self.alphabet)?;
+        self.readings = Self::build_readings(&self.faces, &self.alphabet)?;
+
+        Ok(self)
+    }
+
+    /// Changes how the cells of the board connect to each other
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        if self.topology != topology {
+            self.topology = topology;
+            self.neighbours = self.build_neighbours();
+        }
+
+        self
+    }
+
+    /// Sets the point values of letters for Ruzzle and Wordament style scoring
+    pub fn with_letter_values(mut self, letter_values: LetterValues) -> Self {
+        self.letter_values = Some(letter_values);
+        self.elem_values = self.build_elem_values();
+
+        self
+    }
+
+    /// Sets the score multiplier of each cell for Ruzzle and Wordament style scoring
+    pub fn with_bonuses(mut self, bonuses: Vec<CellBonus>) -> Result<Self, String> {
+        if bonuses.len() != self.cell_count() {
+            Err(format!(
+                "Board has {} cells but {} cell bonuses given",
+                self.cell_count(),
+                bonuses.len()
+            ))?
+        }
+
+        self.bonuses = bonuses;
+
+        Ok(self)
     }
 
-    /// Prints the Boggle board
+    /// Prints the Boggle board. Odd rows of hexagonal boards are printed offset to the right
+    /// and the layers of 3D boards are printed one after the other
     pub fn print(&self) {
-        let longest = self
-            .faces
-            .iter()
-            .flatten()
-            .map(|f| match f {
-                DiceFace::Ligature(s) => s.len(),
-                _ => 1,
-            })
-            .max()
-            .unwrap_or(1);
+        self.print_cells(|cell| self.faces[cell].to_string());
+    }
+
+    /// Prints the score multiplier of each cell laid out as the board
+    pub fn print_bonuses(&self) {
+        self.print_cells(|cell| self.bonuses[cell].to_string());
+    }
+
+    /// Prints a string for each cell laid out as the board, such as the face with a marker
+    pub fn print_cells<F>(&self, cell_string: F)
+    where
+        F: Fn(usize) -> String,
+    {
+        let strings = (0..self.cell_count()).map(cell_string).collect::<Vec<_>>();
+
+        let longest = strings.iter().map(|s| s.chars().count()).max().unwrap_or(1);
+
+        for z in 0..self.z {
+            if self.z > 1 {
+                if z > 0 {
+                    println!();
+                }
+
+                println!("Layer {}:", z + 1);
+            }
 
-        for y in 0..self.y {
-            for x in 0..self.x {
-                print!(" {:<longest$}", self.faces[y][x]);
+            for y in 0..self.y {
+                // Shift odd rows of hexagonal boards by half a cell
+                if self.topology == Topology::Hex && y % 2 == 1 {
+                    print!("{:1$}", "", longest.div_ceil(2));
+                }
+
+                for x in 0..self.x {
+                    match self.cell_at_layer(x, y, z) {
+                        Some(cell) => print!(" {:<longest$}", strings[cell]),
+                        None => print!(" {:<longest$}", ""),
+                    }
+                }
+                println!()
             }
-            println!()
         }
     }
 
@@ -157,64 +410,427 @@ impl Board {
         (self.x, self.y)
     }
 
+    /// Returns the number of layers of the Boggle board. This is 1 except for 3D boards
+    pub fn depth(&self) -> usize {
+        self.z
+    }
+
+    /// Returns the alphabet used by the Boggle board
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// Returns the topology of the Boggle board
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
     /// Returns the dice face at a given position on the Boggle board
     pub fn face(&self, x: usize, y: usize) -> DiceFace {
-        self.faces[y][x].clone()
+        self.faces[self.cell(x, y)].clone()
     }
 
     /// Returns the dictionary entry elements for a dice face on the Boggle board
     pub fn dict_ents(&self, x: usize, y: usize) -> &Vec<u8> {
-        &self.dict_ents[y][x]
+        &self.dict_ents[self.cell(x, y)]
     }
 
     /// Returns a vector of dice neighbours on the Boggle board
     pub fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
-        let mut result = Vec::new();
+        self.neighbours[self.cell(x, y)]
+            .iter()
+            .map(|c| self.cell_pos(*c))
+            .collect()
+    }
 
-        if x > 0 {
-            result.push((x - 1, y));
-            if y > 0 {
-                result.push((x - 1, y - 1));
-            }
-            if y < self.y - 1 {
-                result.push((x - 1, y + 1));
-            }
-        }
-        if y > 0 {
-            result.push((x, y - 1));
+    /// Returns the number of cells on the Boggle board
+    #[inline]
+    pub fn cell_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns the cell number for a position on the Boggle board
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no cell at the position on a shaped board
+    #[inline]
+    pub fn cell(&self, x: usize, y: usize) -> usize {
+        self.cell_at(x, y)
+            .unwrap_or_else(|| panic!("No cell at position ({x},{y})"))
+    }
+
+    /// Returns the cell number for a position on the Boggle board, or None if the position
+    /// is empty or outside the board. On 3D boards the position is on the first layer
+    #[inline]
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<usize> {
+        self.cell_at_layer(x, y, 0)
+    }
+
+    /// Returns the cell number for a position on a layer of the Boggle board, or None if the
+    /// position is empty or outside the board
+    #[inline]
+    pub fn cell_at_layer(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        if x < self.x && y < self.y && z < self.z {
+            self.grid[(((z * self.y) + y) * self.x) + x]
+        } else {
+            None
         }
+    }
+
+    /// Returns the position on the Boggle board of a cell number. On 3D boards this is the
+    /// position within the cell's layer
+    #[inline]
+    pub fn cell_pos(&self, cell: usize) -> (usize, usize) {
+        let (x, y, _) = self.positions[cell];
+        (x, y)
+    }
+
+    /// Returns the (x, y, z) coordinates of a cell number, where z is the layer. z is always
+    /// 0 on flat boards
+    #[inline]
+    pub fn cell_coords(&self, cell: usize) -> (usize, usize, usize) {
+        self.positions[cell]
+    }
+
+    /// Returns true if every position of the board's grid has a cell
+    pub fn is_rectangular(&self) -> bool {
+        self.cell_count() == self.x * self.y * self.z
+    }
+
+    /// Returns the dice face for a cell
+    #[inline]
+    pub fn cell_face(&self, cell: usize) -> &DiceFace {
+        &self.faces[cell]
+    }
+
+    /// Returns the dictionary entry elements for a cell. Blank, wildcard and alternatives
+    /// faces have none
+    #[inline]
+    pub fn cell_dict_ents(&self, cell: usize) -> &[u8] {
+        &self.dict_ents[cell]
+    }
+
+    /// Returns the dictionary entry elements for each reading of an alternatives face in a
+    /// cell. Other faces have none
+    #[inline]
+    pub fn cell_readings(&self, cell: usize) -> &[Vec<u8>] {
+        &self.readings[cell]
+    }
+
+    /// Returns the precomputed neighbouring cell numbers of a cell
+    #[inline]
+    pub fn cell_neighbours(&self, cell: usize) -> &[usize] {
+        &self.neighbours[cell]
+    }
+
+    /// Returns the score multiplier of a cell
+    #[inline]
+    pub fn cell_bonus(&self, cell: usize) -> CellBonus {
+        self.bonuses[cell]
+    }
+
+    /// Returns true if any cell has a score multiplier
+    pub fn has_bonuses(&self) -> bool {
+        self.bonuses.iter().any(|b| *b != CellBonus::None)
+    }
+
+    /// Returns the point values of letters if set
+    pub fn letter_values(&self) -> Option<&LetterValues> {
+        self.letter_values.as_ref()
+    }
+
+    /// Returns the point value of a dictionary letter element, or 0 if no letter values are
+    /// set
+    #[inline]
+    pub fn elem_value(&self, elem: u8) -> u32 {
+        self.elem_values.get(elem as usize).copied().unwrap_or(0)
+    }
+
+    /// Builds a table of neighbouring cell numbers for each cell of the board from the grid
+    /// topology and the extra links
+    fn build_neighbours(&self) -> Vec<Vec<usize>> {
+        let grid_neighbours = self.topology.build_neighbours(self.x, self.y, self.z);
 
-        if x < self.x - 1 {
-            result.push((x + 1, y));
-            if y < self.y - 1 {
-                result.push((x + 1, y + 1));
+        let mut result = self
+            .positions
+            .iter()
+            .map(|&(x, y, z)| {
+                grid_neighbours[(((z * self.y) + y) * self.x) + x]
+                    .iter()
+                    .filter_map(|pos| self.grid[*pos])
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for &(a, b) in &self.links {
+            if !result[a].contains(&b) {
+                result[a].push(b);
             }
-            if y > 0 {
-                result.push((x + 1, y - 1));
+
+            if !result[b].contains(&a) {
+                result[b].push(a);
             }
         }
-        if y < self.y - 1 {
-            result.push((x, y + 1));
-        }
 
         result
     }
 
-    /// Builds a 3d vector of dictionary entries from a 2d vector of dice faces
-    fn build_dict_ents(faces: &[Vec<DiceFace>]) -> Vec<Vec<Vec<u8>>> {
+    /// Builds a table of point values indexed by dictionary letter element
+    fn build_elem_values(&self) -> Vec<u32> {
+        match &self.letter_values {
+            Some(values) => (0..=self.alphabet.len())
+                .map(|e| match e {
+                    0 => 0,
+                    e => values.value(self.alphabet.elem_to_uchar(e as u8)),
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Builds a 2d vector of dictionary entries from a vector of dice faces
+    fn build_dict_ents(faces: &[DiceFace], alphabet: &Alphabet) -> Result<Vec<Vec<u8>>, String> {
         faces
             .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|face| match face {
-                        DiceFace::Letter(c) => vec![Dictionary::uchar_to_elem(*c)],
-                        DiceFace::Ligature(str) => {
-                            str.chars().map(Dictionary::uchar_to_elem).collect()
-                        }
-                        DiceFace::Stop => vec![],
-                    })
-                    .collect()
+            .map(|face| match face {
+                DiceFace::Letter(c) => letter_elems(&c.to_string(), alphabet),
+                DiceFace::Ligature(str) => letter_elems(str, alphabet),
+                DiceFace::Stop | DiceFace::Wildcard | DiceFace::Alternatives(_) => Ok(vec![]),
             })
             .collect()
     }
+
+    /// Builds the dictionary entries of each reading of the alternatives faces in a vector of
+    /// dice faces
+    fn build_readings(
+        faces: &[DiceFace],
+        alphabet: &Alphabet,
+    ) -> Result<Vec<Vec<Vec<u8>>>, String> {
+        faces
+            .iter()
+            .map(|face| match face {
+                DiceFace::Alternatives(readings) => {
+                    readings.iter().map(|r| letter_elems(r, alphabet)).collect()
+                }
+                _ => Ok(vec![]),
+            })
+            .collect()
+    }
+}
+
+/// Converts a string of dice letters to dictionary entries
+fn letter_elems(letters: &str, alphabet: &Alphabet) -> Result<Vec<u8>, String> {
+    letters
+        .chars()
+        .map(|c| {
+            alphabet
+                .char_to_elem(c)
+                .ok_or_else(|| format!("Dice letter {c} is not in the alphabet {alphabet}"))
+        })
+        .collect()
+}
+
+/// Formats board dimensions, including the depth for 3D boards
+fn size_string(x: usize, y: usize, z: usize) -> String {
+    if z == 1 {
+        format!("{x}x{y}")
+    } else {
+        format!("{x}x{y}x{z}")
+    }
+}
+
+/// Formats a position, including the layer for 3D boards
+fn pos_string(x: usize, y: usize, z: usize, depth: usize) -> String {
+    if depth == 1 {
+        format!("({x},{y})")
+    } else {
+        format!("({x},{y},{z})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_string(board: &Board) -> String {
+        let (x, y) = board.dimension();
+
+        (0..y)
+            .map(|y| {
+                (0..x)
+                    .map(|x| board.face(x, y).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[test]
+    fn seeded() {
+        let board1 = Board::new_random_seeded(GameType::Classic, 1234);
+        let board2 = Board::new_random_seeded(GameType::Classic, 1234);
+        let board3 = Board::new_random_seeded(GameType::Classic, 4321);
+
+        assert_eq!(board_string(&board1), board_string(&board2));
+        assert_ne!(board_string(&board1), board_string(&board3));
+
+        // ChaCha8 output is fixed across rand releases so published seeds keep their boards
+        assert_eq!(board_string(&board1), "O E W T/A E B N/E I K F/A T V I");
+    }
+
+    #[test]
+    fn neighbours() {
+        let board = Board::new(Some(3), None, vec!["A".to_string(); 9]).unwrap();
+
+        let mut corner = board.neighbours(0, 0);
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+
+        assert_eq!(board.cell_neighbours(board.cell(1, 1)).len(), 8);
+        assert_eq!(board.cell_neighbours(board.cell(2, 1)).len(), 5);
+
+        let board = board.with_topology(Topology::Toroidal);
+
+        let mut corner = board.neighbours(0, 0);
+        corner.sort();
+        assert_eq!(
+            corner,
+            vec![
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_size() {
+        assert!(Board::new(Some(3), Some(3), vec!["A".to_string(); 8]).is_err());
+
+        let faces = vec![DiceFace::Letter('A'); 8];
+        let english = Arc::new(Alphabet::english());
+        assert!(Board::new_from_faces(2, 2, 2, faces.clone(), &english).is_ok());
+        assert!(Board::new_from_faces(3, 3, 1, faces, &english).is_err());
+    }
+
+    #[test]
+    fn cube() {
+        let faces = (0..27)
+            .map(|i| char::from(b'A' + (i % 26) as u8).to_string())
+            .collect::<Vec<_>>();
+
+        let board =
+            Board::new_with_depth(None, None, 3, faces.clone(), &Alphabet::english()).unwrap();
+
+        assert_eq!(board.dimension(), (3, 3));
+        assert_eq!(board.depth(), 3);
+        assert_eq!(board.cell_coords(13), (1, 1, 1));
+        assert_eq!(board.cell_pos(13), (1, 1));
+        assert_eq!(board.cell_at_layer(2, 2, 2), Some(26));
+        assert_eq!(board.cell_face(9), &DiceFace::Letter('J'));
+
+        // Centre cell touches every other cell
+        assert_eq!(board.cell_neighbours(13).len(), 26);
+
+        // Faces must split into layers
+        assert!(Board::new_with_depth(None, None, 2, faces, &Alphabet::english()).is_err());
+
+        // Random 3D boards from a dice set
+        let set = DiceSet::from_toml(
+            r#"
+            name = "Cube"
+            width = 2
+            height = 2
+            depth = 2
+            dice = ["AB", "CD", "EF", "GH", "IJ", "KL", "MN", "OP"]
+            "#,
+        )
+        .unwrap();
+
+        let board = Board::new_random_seeded(GameType::Custom(set), 1);
+
+        assert_eq!(board.depth(), 2);
+        assert_eq!(board.cell_count(), 8);
+        assert_eq!(board.cell_neighbours(0).len(), 7);
+    }
+
+    #[test]
+    fn custom() {
+        let set = DiceSet::from_toml(
+            r#"
+            name = "Umlaut"
+            width = 2
+            height = 1
+            alphabet = "ÄÖÜ"
+            dice = ["Ä", "ÖÜ"]
+            "#,
+        )
+        .unwrap();
+
+        let board = Board::new_random_seeded(GameType::Custom(set), 1);
+
+        assert_eq!(board.dimension(), (2, 1));
+        assert_eq!(board.alphabet().len(), 3);
+        assert!(["Ä Ö", "Ä Ü", "Ö Ä", "Ü Ä"].contains(&board_string(&board).as_str()));
+    }
+
+    #[test]
+    fn alphabet() {
+        let faces = ["ä", "B", "Ñ", "d"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>();
+
+        // Not in the English alphabet
+        assert!(Board::new(None, None, faces.clone()).is_err());
+
+        let alphabet = Alphabet::new("ABCDÄÑ").unwrap();
+        let board = Board::new_with_alphabet(None, None, faces, &alphabet).unwrap();
+
+        assert_eq!(board_string(&board), "Ä B/Ñ D");
+        assert_eq!(board.cell_dict_ents(0), &[5]);
+        assert_eq!(board.cell_dict_ents(2), &[6]);
+
+        let board = Board::new(None, None, vec!["A".to_string(); 4]).unwrap();
+        assert!(board.with_alphabet(&alphabet).is_ok());
+    }
+
+    #[test]
+    fn scoring() {
+        let board = Board::new(None, None, ["Q", "A", "B", "C"].map(String::from).to_vec())
+            .unwrap()
+            .with_letter_values(LetterValues::from_string("Q=10 A=1 C=3").unwrap());
+
+        assert_eq!(board.elem_value(17), 10);
+        assert_eq!(board.elem_value(1), 1);
+        assert_eq!(board.elem_value(2), 0);
+        assert!(!board.has_bonuses());
+
+        let err = board
+            .clone()
+            .with_bonuses(vec![CellBonus::DoubleWord])
+            .err()
+            .unwrap();
+        assert_eq!(err, "Board has 4 cells but 1 cell bonuses given");
+
+        let board = board
+            .with_bonuses(CellBonus::from_list(". TL . DW").unwrap())
+            .unwrap();
+        assert!(board.has_bonuses());
+        assert_eq!(board.cell_bonus(1), CellBonus::TripleLetter);
+
+        // Values follow a change of alphabet
+        let board = board
+            .with_alphabet(&Alphabet::new("QABC").unwrap())
+            .unwrap();
+        assert_eq!(board.elem_value(1), 10);
+        assert_eq!(board.elem_value(4), 3);
+    }
 }
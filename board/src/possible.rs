@@ -0,0 +1,230 @@
+use crate::{pos_string, Board, DiceFace, GameType};
+
+impl Board {
+    /// Returns true if the board could be rolled with the dice of a game type
+    pub fn is_possible_with(&self, game_type: &GameType) -> bool {
+        self.check_possible_with(game_type).is_ok()
+    }
+
+    /// Checks if the board could be rolled with the dice of a game type by matching each cell
+    /// to a different dice showing the cell's face. When the board is impossible the error
+    /// describes the over-represented faces or the cells which can't all be given a dice
+    pub fn check_possible_with(&self, game_type: &GameType) -> Result<(), String> {
+        let (x, y) = self.dimension();
+        let (dx, dy) = game_type.layout();
+
+        if (x, y) != (dx, dy) {
+            Err(format!(
+                "Board is {x}x{y} but {} uses a {dx}x{dy} board",
+                game_type.name()
+            ))?
+        }
+
+        let (z, dz) = (self.depth(), game_type.depth());
+
+        if z != dz {
+            Err(format!(
+                "Board has {z} {} but {} uses {dz}",
+                if z == 1 { "layer" } else { "layers" },
+                game_type.name()
+            ))?
+        }
+
+        if !self.is_rectangular() {
+            Err(format!(
+                "Board has {} cells but {} uses a full board",
+                self.cell_count(),
+                game_type.name()
+            ))?
+        }
+
+        let dice = game_type.dice();
+
+        // Build list of dice which can show each cell's face
+        let candidates = (0..self.cell_count())
+            .map(|cell| {
+                let face = self.cell_face(cell);
+
+                dice.iter()
+                    .enumerate()
+                    .filter(|(_, d)| d.faces().iter().any(|f| f.matches(face)))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Check for faces appearing more times than there are dice showing them
+        let mut problems = Vec::new();
+        let mut checked: Vec<&DiceFace> = Vec::new();
+
+        for (cell, dice_list) in candidates.iter().enumerate() {
+            let face = self.cell_face(cell);
+
+            if checked.contains(&face) {
+                continue;
+            }
+
+            checked.push(face);
+
+            let count = (cell..self.cell_count())
+                .filter(|c| self.cell_face(*c) == face)
+                .count();
+            let available = dice_list.len();
+
+            if count > available {
+                problems.push(format!(
+                    "{} appears {count} {} but {} it",
+                    face_name(face),
+                    if count == 1 { "time" } else { "times" },
+                    match available {
+                        0 => "no dice has".to_string(),
+                        1 => "only 1 dice has".to_string(),
+                        n => format!("only {n} dice have"),
+                    },
+                ));
+            }
+        }
+
+        if !problems.is_empty() {
+            Err(problems.join(", "))?
+        }
+
+        // Match cells to dice
+        let mut dice_cell = vec![None; dice.len()];
+
+        for cell in 0..self.cell_count() {
+            let mut seen = vec![false; dice.len()];
+
+            if !assign(cell, &candidates, &mut dice_cell, &mut seen) {
+                // The cell and the cells holding the dice it could use need more dice than
+                // can show their faces
+                let mut cells = vec![cell];
+
+                cells.extend(
+                    seen.iter()
+                        .enumerate()
+                        .filter(|(_, s)| **s)
+                        .filter_map(|(d, _)| dice_cell[d]),
+                );
+                cells.sort();
+
+                let usable = seen
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| **s)
+                    .map(|(d, _)| dice[d].to_string())
+                    .collect::<Vec<_>>();
+
+                Err(format!(
+                    "Cells {} need {} different dice but only {} can show their faces: {}",
+                    cells
+                        .iter()
+                        .map(|c| {
+                            let (x, y, z) = self.cell_coords(*c);
+                            format!(
+                                "{} {}",
+                                pos_string(x, y, z, self.depth()),
+                                face_name(self.cell_face(*c))
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    cells.len(),
+                    usable.len(),
+                    usable.join(" ")
+                ))?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tries to assign a dice to a cell, moving previously assigned cells to other dice if needed
+fn assign(
+    cell: usize,
+    candidates: &[Vec<usize>],
+    dice_cell: &mut [Option<usize>],
+    seen: &mut [bool],
+) -> bool {
+    for &dice in &candidates[cell] {
+        if !seen[dice] {
+            seen[dice] = true;
+
+            let free = match dice_cell[dice] {
+                None => true,
+                Some(other) => assign(other, candidates, dice_cell, seen),
+            };
+
+            if free {
+                dice_cell[dice] = Some(cell);
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Returns a printable name for a dice face
+fn face_name(face: &DiceFace) -> String {
+    match face {
+        DiceFace::Stop => "Blank".to_string(),
+        _ => face.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_board(faces: &str) -> Board {
+        Board::new(None, None, faces.split(' ').map(String::from).collect()).unwrap()
+    }
+
+    #[test]
+    fn possible() {
+        // Seeded boards are always possible
+        for seed in 0..20 {
+            let board = Board::new_random_seeded(GameType::Classic, seed);
+            assert!(board.is_possible_with(&GameType::Classic));
+        }
+
+        let board = Board::new_random_seeded(GameType::SuperBig, 1);
+        assert!(board.is_possible_with(&GameType::SuperBig));
+        assert!(!board.is_possible_with(&GameType::Classic));
+
+        // The classic Q/QU face can be transcribed as any of its readings
+        for qu in ["Q/QU", "QU", "Q"] {
+            let board = make_board(&format!("{qu} A C I O T A B L E S T D N K E"));
+            assert!(board.is_possible_with(&GameType::Classic));
+        }
+    }
+
+    #[test]
+    fn over_represented() {
+        // Two QU faces on a classic board
+        let board = make_board("QU A C I O T A B L E S QU D N K E");
+
+        let err = board.check_possible_with(&GameType::Classic).unwrap_err();
+        assert_eq!(err, "QU appears 2 times but only 1 dice has it");
+
+        // Blank face on a classic board
+        let board = make_board("A A C I O T A B L E S  D N K E");
+
+        let err = board.check_possible_with(&GameType::Classic).unwrap_err();
+        assert_eq!(err, "Blank appears 1 time but no dice has it");
+    }
+
+    #[test]
+    fn matching() {
+        // J and QU are only on the same classic dice (ABJMO1)
+        let board = make_board("J A C I O T A B L E S QU D N K E");
+
+        assert_eq!(
+            board.check_possible_with(&GameType::Classic).unwrap_err(),
+            "Cells (0,0) J, (3,2) QU need 2 different dice but only 1 can show their faces: \
+             [A B J M O Q/QU]"
+        );
+    }
+}
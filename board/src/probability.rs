@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+
+use crate::{Board, DiceFace, GameType};
+
+/// Probabilities of a dice face being rolled with a game type's dice
+#[derive(Clone, Debug)]
+pub struct FaceProbability {
+    /// The dice face
+    pub face: DiceFace,
+    /// Probability of any single cell showing the face. This is the same for every cell
+    /// as the dice are shuffled before being placed
+    pub cell: f64,
+    /// Probability of the face appearing at least once on the board
+    pub appears: f64,
+    /// Expected number of times the face appears on the board
+    pub expected: f64,
+}
+
+impl GameType {
+    /// Calculates the probabilities of each dice face being rolled, most likely first
+    pub fn face_probabilities(&self) -> Vec<FaceProbability> {
+        let dice = self.dice();
+        let (x, y) = self.layout();
+        let cells = x * y * self.depth();
+
+        // Collect distinct faces
+        let mut faces: Vec<DiceFace> = Vec::new();
+
+        for face in dice.iter().flat_map(|d| d.faces()) {
+            if !faces.contains(face) {
+                faces.push(face.clone());
+            }
+        }
+
+        let mut result = faces
+            .into_iter()
+            .map(|face| {
+                let probs = dice
+                    .iter()
+                    .map(|d| face_probability(d.faces(), &face))
+                    .collect::<Vec<_>>();
+
+                let sum = probs.iter().sum::<f64>();
+
+                // Probability none of the dice chosen for the board show the face
+                let none =
+                    choose_mean_product(&probs.iter().map(|p| 1.0 - p).collect::<Vec<_>>(), cells);
+
+                FaceProbability {
+                    face,
+                    cell: sum / dice.len() as f64,
+                    appears: 1.0 - none,
+                    expected: sum * cells as f64 / dice.len() as f64,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_by(|a, b| {
+            b.cell
+                .total_cmp(&a.cell)
+                .then_with(|| a.face.to_string().cmp(&b.face.to_string()))
+        });
+
+        result
+    }
+}
+
+impl Board {
+    /// Calculates the exact probability of the board being rolled with the dice of a game type.
+    /// Dice are shuffled into the board positions and each dice shows a random face as in
+    /// new_random. Returns 0 if the board can't be rolled with the dice
+    pub fn probability_with(&self, game_type: &GameType) -> f64 {
+        if self.dimension() != game_type.layout()
+            || self.depth() != game_type.depth()
+            || !self.is_rectangular()
+        {
+            return 0.0;
+        }
+
+        let dice = game_type.dice();
+        let cells = self.cell_count();
+
+        if dice.len() < cells {
+            return 0.0;
+        }
+
+        // Count distinct faces on the board
+        let mut faces: Vec<(&DiceFace, usize)> = Vec::new();
+
+        for cell in 0..cells {
+            let face = self.cell_face(cell);
+
+            match faces.iter_mut().find(|(f, _)| *f == face) {
+                Some((_, count)) => *count += 1,
+                None => faces.push((face, 1)),
+            }
+        }
+
+        // Each state is the number of cells of each face covered so far, stored in mixed radix
+        let mut strides = Vec::with_capacity(faces.len());
+        let mut stride = 1;
+
+        for (_, count) in &faces {
+            strides.push(stride);
+            stride *= count + 1;
+        }
+
+        let target = faces
+            .iter()
+            .zip(&strides)
+            .map(|((_, count), stride)| count * stride)
+            .sum::<usize>();
+
+        // Probability of each dice showing each face
+        let mut dice_probs = dice
+            .iter()
+            .map(|d| {
+                faces
+                    .iter()
+                    .map(|(face, _)| face_probability(d.faces(), face))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Number of dice which can show each face
+        let showing = (0..faces.len())
+            .map(|t| dice_probs.iter().filter(|p| p[t] > 0.0).count())
+            .collect::<Vec<_>>();
+
+        // Process dice showing the rarest faces first so that states for those faces are
+        // completed early, and dice which can't show any of the faces last
+        dice_probs.sort_by_key(|p| {
+            (0..faces.len())
+                .filter(|t| p[*t] > 0.0)
+                .map(|t| showing[t])
+                .min()
+                .unwrap_or(usize::MAX)
+        });
+
+        // Number of remaining dice which can show each face after each dice is processed
+        let mut available = vec![vec![0; faces.len()]; dice.len()];
+
+        for i in (0..dice.len() - 1).rev() {
+            for t in 0..faces.len() {
+                available[i][t] = available[i + 1][t] + usize::from(dice_probs[i + 1][t] > 0.0);
+            }
+        }
+
+        // Sum the probability products of every way of choosing a dice and face for each cell,
+        // processing one dice at a time. Each dice is either unused or shows one of the faces.
+        // States which can no longer be completed by the remaining dice are dropped
+        let mut states = HashMap::from([(0usize, 1.0f64)]);
+
+        for (i, probs) in dice_probs.iter().enumerate() {
+            let remaining = dice.len() - i - 1;
+            let mut next = HashMap::with_capacity(states.len() * 2);
+
+            let add = |state: usize| {
+                let mut needed = 0;
+
+                for (t, (_, count)) in faces.iter().enumerate() {
+                    let face_needed = count - ((state / strides[t]) % (count + 1));
+
+                    if face_needed > available[i][t] {
+                        return false;
+                    }
+
+                    needed += face_needed;
+                }
+
+                needed <= remaining
+            };
+
+            for (state, prob) in states {
+                // Dice unused
+                if add(state) {
+                    *next.entry(state).or_insert(0.0) += prob;
+                }
+
+                // Dice shows one of the faces
+                for (t, p) in probs.iter().enumerate() {
+                    if *p > 0.0 && (state / strides[t]) % (faces[t].1 + 1) < faces[t].1 {
+                        let new_state = state + strides[t];
+
+                        if add(new_state) {
+                            *next.entry(new_state).or_insert(0.0) += prob * p;
+                        }
+                    }
+                }
+            }
+
+            states = next;
+        }
+
+        let sum = states.get(&target).copied().unwrap_or(0.0);
+
+        // Multiply by the number of ways of arranging cells with the same face and by the
+        // probability of each arrangement of dice
+        let arrangements = faces
+            .iter()
+            .map(|(_, count)| factorial(*count))
+            .product::<f64>();
+        let dice_order = (dice.len() - cells + 1..=dice.len())
+            .map(|n| n as f64)
+            .product::<f64>();
+
+        sum * arrangements / dice_order
+    }
+
+    /// Returns the probability of each cell of the board showing its face with the dice of a
+    /// game type
+    pub fn cell_probabilities(&self, game_type: &GameType) -> Vec<f64> {
+        let faces = game_type.face_probabilities();
+
+        (0..self.cell_count())
+            .map(|cell| {
+                faces
+                    .iter()
+                    .find(|f| f.face.matches(self.cell_face(cell)))
+                    .map(|f| f.cell)
+                    .unwrap_or(0.0)
+            })
+            .collect()
+    }
+}
+
+/// Returns the probability of a dice showing a face
+fn face_probability(faces: &[DiceFace], face: &DiceFace) -> f64 {
+    faces.iter().filter(|f| f.matches(face)).count() as f64 / faces.len() as f64
+}
+
+/// Returns the mean of the product of values over all subsets of a given size
+fn choose_mean_product(values: &[f64], size: usize) -> f64 {
+    // Elementary symmetric polynomials divided by the binomial coefficients
+    let mut e = vec![0.0; size + 1];
+    e[0] = 1.0;
+
+    for (n, v) in values.iter().enumerate() {
+        for k in (1..=size.min(n + 1)).rev() {
+            // Mean of size k subsets of the first n + 1 values
+            let with = k as f64 / (n + 1) as f64;
+            e[k] = (e[k] * (1.0 - with)) + (e[k - 1] * v * with);
+        }
+    }
+
+    e[size]
+}
+
+/// Returns n!
+fn factorial(n: usize) -> f64 {
+    (1..=n).map(|n| n as f64).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiceSet;
+
+    fn tiny() -> GameType {
+        GameType::Custom(
+            DiceSet::from_toml(
+                r#"
+                name = "Tiny"
+                width = 2
+                height = 1
+                dice = ["AB", "AAC", ["B", "QU"]]
+                "#,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn brute_force() {
+        let game_type = tiny();
+        let dice = game_type.dice();
+
+        // Roll every ordered pair of different dice and every face combination
+        let mut boards: HashMap<Vec<String>, f64> = HashMap::new();
+
+        for (i, d1) in dice.iter().enumerate() {
+            for (j, d2) in dice.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                for f1 in d1.faces() {
+                    for f2 in d2.faces() {
+                        let prob =
+                            1.0 / (3.0 * 2.0) / d1.face_count() as f64 / d2.face_count() as f64;
+
+                        *boards
+                            .entry(vec![f1.to_string(), f2.to_string()])
+                            .or_default() += prob;
+                    }
+                }
+            }
+        }
+
+        let mut total = 0.0;
+
+        for (faces, expected) in boards {
+            let board = Board::new(Some(2), Some(1), faces).unwrap();
+            let prob = board.probability_with(&game_type);
+
+            assert!((prob - expected).abs() < 1e-12);
+            total += prob;
+        }
+
+        assert!((total - 1.0).abs() < 1e-12);
+
+        // Not possible with the dice
+        let board = Board::new(Some(2), Some(1), vec!["C".into(), "C".into()]).unwrap();
+        assert_eq!(board.probability_with(&game_type), 0.0);
+    }
+
+    #[test]
+    fn faces() {
+        let faces = tiny().face_probabilities();
+
+        // A is on 3 of 7 faces of the dice
+        assert_eq!(faces[0].face, DiceFace::Letter('A'));
+        assert!((faces[0].cell - ((1.0 / 2.0) + (2.0 / 3.0)) / 3.0).abs() < 1e-12);
+        assert!((faces[0].expected - ((1.0 / 2.0) + (2.0 / 3.0)) * 2.0 / 3.0).abs() < 1e-12);
+
+        // QU only appears if the third dice is chosen and shows QU
+        let qu = faces
+            .iter()
+            .find(|f| f.face == DiceFace::Ligature("QU".into()))
+            .unwrap();
+        assert!((qu.appears - (2.0 / 3.0) * 0.5).abs() < 1e-12);
+
+        for game_type in [GameType::Classic, GameType::SuperBig] {
+            let faces = game_type.face_probabilities();
+            let cells = faces.iter().map(|f| f.cell).sum::<f64>();
+            let expected = faces.iter().map(|f| f.expected).sum::<f64>();
+            let (x, y) = game_type.layout();
+            let z = game_type.depth();
+
+            assert!((cells - 1.0).abs() < 1e-12);
+            assert!((expected - (x * y * z) as f64).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn classic() {
+        // Two QU faces can't be rolled
+        let board = Board::new(
+            None,
+            None,
+            "QU A C I O T A B L E S QU D N K E"
+                .split(' ')
+                .map(String::from)
+                .collect(),
+        )
+        .unwrap();
+
+        assert_eq!(board.probability_with(&GameType::Classic), 0.0);
+
+        // Rolled boards are possible
+        let board = Board::new_random_seeded(GameType::Classic, 1);
+        let prob = board.probability_with(&GameType::Classic);
+
+        assert!(prob > 0.0 && prob < 1e-15);
+        assert_eq!(board.probability_with(&GameType::BigOriginal), 0.0);
+
+        // Per cell probability of a classic E (10 of the 96 faces)
+        let cells = Board::new(None, None, vec!["E".to_string(); 16])
+            .unwrap()
+            .cell_probabilities(&GameType::Classic);
+
+        assert!(cells.iter().all(|p| (p - 10.0 / 96.0).abs() < 1e-12));
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Score multiplier on a board cell for Ruzzle and Wordament style scoring
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CellBonus {
+    /// No bonus
+    #[default]
+    None,
+    /// Doubles the value of the cell's letters
+    DoubleLetter,
+    /// Triples the value of the cell's letters
+    TripleLetter,
+    /// Doubles the value of a word using the cell
+    DoubleWord,
+    /// Triples the value of a word using the cell
+    TripleWord,
+}
+
+impl CellBonus {
+    /// Parses a cell bonus code: `.` (none), `DL`, `TL`, `DW` or `TW`
+    pub fn from_string(string: &str) -> Result<Self, String> {
+        match string.to_uppercase().as_str() {
+            "." => Ok(CellBonus::None),
+            "DL" => Ok(CellBonus::DoubleLetter),
+            "TL" => Ok(CellBonus::TripleLetter),
+            "DW" => Ok(CellBonus::DoubleWord),
+            "TW" => Ok(CellBonus::TripleWord),
+            _ => Err(format!(
+                "Invalid cell bonus {string} (expected one of ., DL, TL, DW, TW)"
+            )),
+        }
+    }
+
+    /// Parses a list of cell bonus codes separated by spaces or commas
+    pub fn from_list(string: &str) -> Result<Vec<Self>, String> {
+        string
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(Self::from_string)
+            .collect()
+    }
+
+    /// Returns the multiplier for the value of the cell's letters
+    pub fn letter_multiplier(&self) -> u32 {
+        match self {
+            CellBonus::DoubleLetter => 2,
+            CellBonus::TripleLetter => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the multiplier for the value of a word using the cell
+    pub fn word_multiplier(&self) -> u32 {
+        match self {
+            CellBonus::DoubleWord => 2,
+            CellBonus::TripleWord => 3,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for CellBonus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            CellBonus::None => ".",
+            CellBonus::DoubleLetter => "DL",
+            CellBonus::TripleLetter => "TL",
+            CellBonus::DoubleWord => "DW",
+            CellBonus::TripleWord => "TW",
+        })
+    }
+}
+
+/// Point values of letters. Letters without a value score nothing
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LetterValues {
+    values: HashMap<char, u32>,
+}
+
+/// English Scrabble letter values
+const SCRABBLE: [(char, u32); 26] = [
+    ('A', 1),
+    ('B', 3),
+    ('C', 3),
+    ('D', 2),
+    ('E', 1),
+    ('F', 4),
+    ('G', 2),
+    ('H', 4),
+    ('I', 1),
+    ('J', 8),
+    ('K', 5),
+    ('L', 1),
+    ('M', 3),
+    ('N', 1),
+    ('O', 1),
+    ('P', 3),
+    ('Q', 10),
+    ('R', 1),
+    ('S', 1),
+    ('T', 1),
+    ('U', 1),
+    ('V', 4),
+    ('W', 4),
+    ('X', 8),
+    ('Y', 4),
+    ('Z', 10),
+];
+
+impl LetterValues {
+    /// Creates letter values from a map of upper case letters to values
+    pub fn new(values: HashMap<char, u32>) -> Self {
+        Self { values }
+    }
+
+    /// Returns the English Scrabble letter values
+    pub fn scrabble() -> Self {
+        Self::new(SCRABBLE.into_iter().collect())
+    }
+
+    /// Returns built in letter values by name (scrabble)
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "scrabble" => Some(Self::scrabble()),
+            _ => None,
+        }
+    }
+
+    /// Parses a list of letter values of the form `A=1,B=3`, separated by commas or spaces
+    pub fn from_string(string: &str) -> Result<Self, String> {
+        let values = string
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(|entry| {
+                let error = || format!("Invalid letter value {entry} (expected LETTER=VALUE)");
+
+                let (letter, value) = entry.split_once('=').ok_or_else(error)?;
+
+                let mut chars = letter.trim().chars();
+
+                let letter = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c.to_uppercase().next().unwrap_or(c),
+                    _ => Err(error())?,
+                };
+
+                Ok((letter, value.trim().parse().map_err(|_| error())?))
+            })
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        Ok(Self::new(values))
+    }
+
+    /// Returns the value of a letter
+    pub fn value(&self, letter: char) -> u32 {
+        self.values.get(&letter).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonus() {
+        assert_eq!(
+            CellBonus::from_list(". dl, TL\nDW TW").unwrap(),
+            vec![
+                CellBonus::None,
+                CellBonus::DoubleLetter,
+                CellBonus::TripleLetter,
+                CellBonus::DoubleWord,
+                CellBonus::TripleWord
+            ]
+        );
+        assert_eq!(CellBonus::TripleLetter.letter_multiplier(), 3);
+        assert_eq!(CellBonus::TripleLetter.word_multiplier(), 1);
+        assert_eq!(CellBonus::DoubleWord.word_multiplier(), 2);
+        assert_eq!(CellBonus::DoubleWord.to_string(), "DW");
+        assert!(CellBonus::from_string("QW").is_err());
+    }
+
+    #[test]
+    fn values() {
+        let values = LetterValues::scrabble();
+        assert_eq!(values.value('Q'), 10);
+        assert_eq!(values.value('É'), 0);
+
+        let values = LetterValues::from_string("a=2, É=5 Z=10").unwrap();
+        assert_eq!(values.value('A'), 2);
+        assert_eq!(values.value('É'), 5);
+        assert_eq!(values.value('B'), 0);
+
+        assert!(LetterValues::from_string("AB=1").is_err());
+        assert!(LetterValues::from_string("A1").is_err());
+        assert!(LetterValues::from_string("A=x").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+/// How the cells of a board connect to each other
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// Rectangular grid where cells at the edges have fewer neighbours
+    #[default]
+    Square,
+    /// Rectangular grid where the left and right edges, and the top and bottom edges, are
+    /// adjacent so every cell has 8 neighbours
+    Toroidal,
+    /// Hexagonal grid in offset rows where odd rows are shifted half a cell to the right, so
+    /// every cell has up to 6 neighbours
+    Hex,
+    /// Cells are only adjacent where explicitly linked, as on boards loaded from a JSON
+    /// layout file
+    Graph,
+}
+
+impl Topology {
+    /// Names of the topologies
+    pub const NAMES: [&'static str; 4] = ["square", "toroidal", "hex", "graph"];
+
+    /// Returns the topology for a name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "square" => Some(Topology::Square),
+            "toroidal" => Some(Topology::Toroidal),
+            "hex" => Some(Topology::Hex),
+            "graph" => Some(Topology::Graph),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the topology
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topology::Square => "square",
+            Topology::Toroidal => "toroidal",
+            Topology::Hex => "hex",
+            Topology::Graph => "graph",
+        }
+    }
+
+    /// Builds a table of neighbouring grid positions for each position of a board. On 3D
+    /// boards with more than one layer each cell is also adjacent to the cells directly above
+    /// and below it and to their neighbours in those layers, so cells inside a square 3D board
+    /// have 26 neighbours. Toroidal boards also wrap around between the top and bottom layers
+    pub(crate) fn build_neighbours(&self, bx: usize, by: usize, bz: usize) -> Vec<Vec<usize>> {
+        let layer = match self {
+            Topology::Square => square_neighbours(bx, by),
+            Topology::Toroidal => toroidal_neighbours(bx, by),
+            Topology::Hex => hex_neighbours(bx, by),
+            Topology::Graph => return vec![vec![]; bx * by * bz],
+        };
+
+        if bz == 1 {
+            return layer;
+        }
+
+        let layer_size = bx * by;
+        let mut result = Vec::with_capacity(layer_size * bz);
+
+        for z in 0..bz {
+            // Layers above and below
+            let mut adjacent = match self {
+                Topology::Toroidal => vec![(z + bz - 1) % bz, (z + 1) % bz],
+                _ => [z.checked_sub(1), (z + 1 < bz).then_some(z + 1)]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            };
+
+            adjacent.dedup();
+
+            for (pos, layer_neighbours) in layer.iter().enumerate() {
+                let mut cell_neighbours = layer_neighbours
+                    .iter()
+                    .map(|n| (z * layer_size) + n)
+                    .collect::<Vec<_>>();
+
+                for az in &adjacent {
+                    for n in [pos].iter().chain(layer_neighbours) {
+                        let neighbour = (az * layer_size) + n;
+
+                        if !cell_neighbours.contains(&neighbour) {
+                            cell_neighbours.push(neighbour);
+                        }
+                    }
+                }
+
+                result.push(cell_neighbours);
+            }
+        }
+
+        result
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Builds the neighbours table for a board with edges
+fn square_neighbours(bx: usize, by: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::with_capacity(bx * by);
+
+    for y in 0..by {
+        for x in 0..bx {
+            let mut cell_neighbours = Vec::with_capacity(8);
+
+            let mut add = |x, y| cell_neighbours.push((y * bx) + x);
+
+            if x > 0 {
+                add(x - 1, y);
+                if y > 0 {
+                    add(x - 1, y - 1);
+                }
+                if y < by - 1 {
+                    add(x - 1, y + 1);
+                }
+            }
+            if y > 0 {
+                add(x, y - 1);
+            }
+
+            if x < bx - 1 {
+                add(x + 1, y);
+                if y < by - 1 {
+                    add(x + 1, y + 1);
+                }
+                if y > 0 {
+                    add(x + 1, y - 1);
+                }
+            }
+            if y < by - 1 {
+                add(x, y + 1);
+            }
+
+            result.push(cell_neighbours);
+        }
+    }
+
+    result
+}
+
+/// Builds the neighbours table for a board which wraps around at the edges. On boards
+/// less than 3 cells wide or high the wrapped neighbours coincide, so each is only added once
+fn toroidal_neighbours(bx: usize, by: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::with_capacity(bx * by);
+
+    for y in 0..by {
+        for x in 0..bx {
+            let cell = (y * bx) + x;
+            let mut cell_neighbours = Vec::with_capacity(8);
+
+            for dy in [by - 1, 0, 1] {
+                for dx in [bx - 1, 0, 1] {
+                    let neighbour = (((y + dy) % by) * bx) + ((x + dx) % bx);
+
+                    if neighbour != cell && !cell_neighbours.contains(&neighbour) {
+                        cell_neighbours.push(neighbour);
+                    }
+                }
+            }
+
+            result.push(cell_neighbours);
+        }
+    }
+
+    result
+}
+
+/// Builds the neighbours table for a hexagonal board in offset rows. Cells in the rows above
+/// and below an even row are at x - 1 and x, and for an odd row at x and x + 1
+fn hex_neighbours(bx: usize, by: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::with_capacity(bx * by);
+
+    for y in 0..by {
+        for x in 0..bx {
+            let mut cell_neighbours = Vec::with_capacity(6);
+
+            let mut add = |x, y| cell_neighbours.push((y * bx) + x);
+
+            // Same row
+            if x > 0 {
+                add(x - 1, y);
+            }
+            if x < bx - 1 {
+                add(x + 1, y);
+            }
+
+            // Rows above and below
+            let (left, right) = if y % 2 == 0 {
+                (x.checked_sub(1), Some(x))
+            } else {
+                (Some(x), (x + 1 < bx).then_some(x + 1))
+            };
+
+            for ny in [y.checked_sub(1), (y + 1 < by).then_some(y + 1)]
+                .into_iter()
+                .flatten()
+            {
+                for nx in [left, right].into_iter().flatten() {
+                    add(nx, ny);
+                }
+            }
+
+            result.push(cell_neighbours);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toroidal() {
+        let neighbours = Topology::Toroidal.build_neighbours(4, 4, 1);
+
+        assert!(neighbours.iter().all(|n| n.len() == 8));
+
+        // Top left corner wraps to the opposite edges
+        let mut corner = neighbours[0].clone();
+        corner.sort();
+        assert_eq!(corner, vec![1, 3, 4, 5, 7, 12, 13, 15]);
+
+        // Narrow boards don't repeat neighbours
+        let neighbours = Topology::Toroidal.build_neighbours(2, 3, 1);
+        assert!(neighbours.iter().all(|n| n.len() == 5));
+
+        let neighbours = Topology::Toroidal.build_neighbours(1, 1, 1);
+        assert!(neighbours[0].is_empty());
+    }
+
+    #[test]
+    fn hex() {
+        //  0  1  2  3
+        //    4  5  6  7
+        //  8  9 10 11
+        let neighbours = Topology::Hex.build_neighbours(4, 3, 1);
+
+        let sorted = |cell: usize| {
+            let mut n = neighbours[cell].clone();
+            n.sort();
+            n
+        };
+
+        assert_eq!(sorted(0), vec![1, 4]);
+        assert_eq!(sorted(5), vec![1, 2, 4, 6, 9, 10]);
+        assert_eq!(sorted(7), vec![3, 6, 11]);
+        assert_eq!(sorted(9), vec![4, 5, 8, 10]);
+
+        // Neighbours are symmetric
+        for (cell, n) in neighbours.iter().enumerate() {
+            assert!(n.iter().all(|o| neighbours[*o].contains(&cell)));
+        }
+    }
+
+    #[test]
+    fn cube() {
+        let neighbours = Topology::Square.build_neighbours(3, 3, 3);
+
+        // Centre cell touches every other cell, corners touch 7
+        assert_eq!(neighbours[13].len(), 26);
+        assert_eq!(neighbours[0].len(), 7);
+        assert_eq!(neighbours[26].len(), 7);
+
+        let mut corner = neighbours[0].clone();
+        corner.sort();
+        assert_eq!(corner, vec![1, 3, 4, 9, 10, 12, 13]);
+
+        // Every cell has 26 neighbours when wrapping around
+        let neighbours = Topology::Toroidal.build_neighbours(4, 4, 4);
+        assert!(neighbours.iter().all(|n| n.len() == 26));
+
+        // Two layers wrapping around only add the other layer once
+        let neighbours = Topology::Toroidal.build_neighbours(3, 3, 2);
+        assert!(neighbours.iter().all(|n| n.len() == 17));
+
+        // Neighbours are symmetric
+        for topology in [Topology::Square, Topology::Hex] {
+            let neighbours = topology.build_neighbours(4, 3, 3);
+
+            for (cell, n) in neighbours.iter().enumerate() {
+                assert!(n.iter().all(|o| neighbours[*o].contains(&cell)));
+            }
+        }
+    }
+
+    #[test]
+    fn names() {
+        for name in Topology::NAMES {
+            assert_eq!(Topology::from_name(name).unwrap().name(), name);
+        }
+    }
+}
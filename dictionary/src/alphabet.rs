@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Maximum number of letters in an alphabet
+pub const MAX_LETTERS: usize = 63;
+
+/// Alphabet of letters shared by a dictionary and a board.
+/// Letters are numbered from 1, element 0 being reserved for the end of word marker
+#[derive(Clone, Debug)]
+pub struct Alphabet {
+    /// Upper case letters
+    letters: Vec<char>,
+    /// Upper case letter to element number
+    upper: HashMap<char, u8>,
+    /// Lower case letter to element number
+    lower: HashMap<char, u8>,
+}
+
+impl Alphabet {
+    /// Creates an alphabet from a string of letters in either case
+    pub fn new(letters: &str) -> Result<Self, String> {
+        let mut alphabet = Self {
+            letters: Vec::new(),
+            upper: HashMap::new(),
+            lower: HashMap::new(),
+        };
+
+        for c in letters.chars() {
+            if !c.is_alphabetic() {
+                Err(format!("Invalid alphabet letter '{c}'"))?
+            }
+
+            let upper = to_upper(c);
+            let lower = to_lower(c);
+
+            if alphabet.upper.contains_key(&upper) {
+                Err(format!("Duplicate alphabet letter '{upper}'"))?
+            }
+
+            alphabet.letters.push(upper);
+
+            let elem = alphabet.letters.len() as u8;
+
+            alphabet.upper.insert(upper, elem);
+            alphabet.lower.insert(lower, elem);
+        }
+
+        if alphabet.letters.is_empty() {
+            Err("Alphabet has no letters")?
+        }
+
+        if alphabet.letters.len() > MAX_LETTERS {
+            Err(format!("Alphabet has more than {MAX_LETTERS} letters"))?
+        }
+
+        Ok(alphabet)
+    }
+
+    /// Creates the 26 letter English alphabet
+    pub fn english() -> Self {
+        Self::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+    }
+
+    /// Creates a predefined alphabet by name
+    pub fn named(name: &str) -> Option<Self> {
+        let letters = match name.to_lowercase().as_str() {
+            "english" => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            "german" => "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜ",
+            "french" => "ABCDEFGHIJKLMNOPQRSTUVWXYZÀÂÆÇÉÈÊËÎÏÔŒÙÛÜŸ",
+            "spanish" => "ABCDEFGHIJKLMNOPQRSTUVWXYZÑ",
+            "danish" | "norwegian" => "ABCDEFGHIJKLMNOPQRSTUVWXYZÆØÅ",
+            "swedish" | "finnish" => "ABCDEFGHIJKLMNOPQRSTUVWXYZÅÄÖ",
+            _ => return None,
+        };
+
+        Some(Self::new(letters).unwrap())
+    }
+
+    /// Returns the names of the predefined alphabets
+    pub fn names() -> &'static [&'static str] {
+        &[
+            "english",
+            "german",
+            "french",
+            "spanish",
+            "danish",
+            "norwegian",
+            "swedish",
+            "finnish",
+        ]
+    }
+
+    /// Returns the number of letters in the alphabet
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Returns true if the alphabet has no letters
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// Returns the upper case letters of the alphabet
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    /// Converts an upper or lower case letter into a dictionary element number
+    #[inline]
+    pub fn char_to_elem(&self, c: char) -> Option<u8> {
+        self.upper.get(&c).or_else(|| self.lower.get(&c)).copied()
+    }
+
+    /// Converts a lower case letter into a dictionary element number
+    #[inline]
+    pub fn lchar_to_elem(&self, c: char) -> Option<u8> {
+        self.lower.get(&c).copied()
+    }
+
+    /// Converts a dictionary element number into an upper case letter
+    #[inline]
+    pub fn elem_to_uchar(&self, e: u8) -> char {
+        self.letters[e as usize - 1]
+    }
+}
+
+impl PartialEq for Alphabet {
+    fn eq(&self, other: &Self) -> bool {
+        // The element maps are built from the letters
+        self.letters == other.letters
+    }
+}
+
+impl Eq for Alphabet {}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.letters.iter().collect::<String>())
+    }
+}
+
+/// Converts a character to upper case if it has a single character upper case form
+fn to_upper(c: char) -> char {
+    let mut upper = c.to_uppercase();
+
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+/// Converts a character to lower case if it has a single character lower case form
+fn to_lower(c: char) -> char {
+    let mut lower = c.to_lowercase();
+
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english() {
+        let alphabet = Alphabet::english();
+
+        assert_eq!(alphabet.len(), 26);
+        assert_eq!(alphabet.char_to_elem('A'), Some(1));
+        assert_eq!(alphabet.char_to_elem('z'), Some(26));
+        assert_eq!(alphabet.lchar_to_elem('Z'), None);
+        assert_eq!(alphabet.char_to_elem('Ä'), None);
+        assert_eq!(alphabet.elem_to_uchar(3), 'C');
+    }
+
+    #[test]
+    fn unicode() {
+        let alphabet = Alphabet::named("German").unwrap();
+
+        assert_eq!(alphabet.len(), 29);
+        assert_eq!(alphabet.char_to_elem('Ä'), Some(27));
+        assert_eq!(alphabet.lchar_to_elem('ü'), Some(29));
+        assert_eq!(alphabet.elem_to_uchar(28), 'Ö');
+
+        let alphabet = Alphabet::new("aøñ").unwrap();
+
+        assert_eq!(alphabet.to_string(), "AØÑ");
+        assert_eq!(alphabet.char_to_elem('Ñ'), Some(3));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Alphabet::new("").is_err());
+        assert!(Alphabet::new("AB1").is_err());
+        assert!(Alphabet::new("ABa").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use flate2::Crc;
+
+use crate::alphabet::MAX_LETTERS;
+use crate::dawg::{Dawg, NODE_WORDS};
+use crate::{Alphabet, WordSizeConstraint};
+
+/// Compiled dictionary file signature
+pub(crate) const MAGIC: &[u8; 8] = b"BOGDICT\0";
+
+/// Compiled dictionary file format version
+pub(crate) const VERSION: u32 = 2;
+
+/// Size of the fixed file header in u32 values
+const HEADER_WORDS: usize = 14;
+
+/// Compiled dictionary file header. All values are little endian.
+///
+/// ```text
+///  0 magic      [u8; 8]
+///  8 version    u32
+/// 12 flags      u32 (reserved, 0)
+/// 16 min length u64
+/// 24 max length u64
+/// 32 words      u64
+/// 40 nodes      u32
+/// 44 edges      u32
+/// 48 checksum   u32 (CRC32 of the alphabet and word graph array)
+/// 52 letters    u32 (number of letters in the alphabet)
+/// 56 alphabet   [u32; letters] (unicode code points of the upper case letters)
+/// .. word graph array [u32; (nodes * 3) + edges]
+/// ```
+pub(crate) struct Header {
+    pub(crate) size: WordSizeConstraint,
+    pub(crate) alphabet: Alphabet,
+    pub(crate) words: usize,
+    pub(crate) nodes: usize,
+    /// Offset of the word graph array in u32 values
+    pub(crate) data_offset: usize,
+}
+
+/// Returns true if the buffer starts with the compiled dictionary signature
+pub(crate) fn is_compiled(buf: &[u8]) -> bool {
+    buf.starts_with(MAGIC)
+}
+
+/// Writes a word graph to a compiled dictionary file
+pub(crate) fn write(
+    file: &str,
+    size: &WordSizeConstraint,
+    alphabet: &Alphabet,
+    words: usize,
+    dawg: &Dawg,
+) -> io::Result<()> {
+    let data = data_bytes(alphabet, dawg);
+
+    let mut writer = BufWriter::new(File::create(file)?);
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&(size.min() as u64).to_le_bytes())?;
+    writer.write_all(&(size.max() as u64).to_le_bytes())?;
+    writer.write_all(&(words as u64).to_le_bytes())?;
+    writer.write_all(&(dawg.node_count() as u32).to_le_bytes())?;
+    writer.write_all(&(dawg.edge_count() as u32).to_le_bytes())?;
+    writer.write_all(&checksum(&data).to_le_bytes())?;
+    writer.write_all(&(alphabet.len() as u32).to_le_bytes())?;
+    writer.write_all(&data)?;
+
+    writer.flush()
+}
+
+/// Returns the data following the header: the alphabet letters then the word graph array
+pub(crate) fn data_bytes(alphabet: &Alphabet, dawg: &Dawg) -> Vec<u8> {
+    alphabet
+        .letters()
+        .iter()
+        .map(|c| *c as u32)
+        .chain(dawg.data().iter().copied())
+        .flat_map(|v| v.to_le_bytes())
+        .collect()
+}
+
+/// Reads and validates the header of a compiled dictionary file
+pub(crate) fn read_header(bytes: &[u8]) -> io::Result<Header> {
+    let header_len = HEADER_WORDS * std::mem::size_of::<u32>();
+
+    if bytes.len() < header_len || !is_compiled(bytes) {
+        Err(invalid("Not a compiled dictionary file"))?
+    }
+
+    let u32_at = |pos: usize| u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap());
+    let u64_at = |pos: usize| u64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap());
+
+    let version = u32_at(8);
+
+    if version != VERSION {
+        Err(invalid(&format!(
+            "Compiled dictionary version {version} is not supported (expected {VERSION})"
+        )))?
+    }
+
+    let nodes = u32_at(40) as usize;
+    let edges = u32_at(44) as usize;
+    let letters = u32_at(52) as usize;
+
+    if letters > MAX_LETTERS {
+        Err(invalid(&format!(
+            "Compiled dictionary alphabet has {letters} letters (at most {MAX_LETTERS} allowed)"
+        )))?
+    }
+
+    let data_len = (letters + Dawg::data_len(nodes, edges)) * std::mem::size_of::<u32>();
+
+    if bytes.len() != header_len + data_len {
+        Err(invalid("Compiled dictionary file is truncated"))?
+    }
+
+    if checksum(&bytes[header_len..]) != u32_at(48) {
+        Err(invalid("Compiled dictionary checksum mismatch"))?
+    }
+
+    let data = &bytes[header_len + (letters * 4)..];
+
+    check_graph(
+        &|i| u32::from_le_bytes(data[i * 4..(i + 1) * 4].try_into().unwrap()),
+        nodes,
+        edges,
+        letters,
+    )
+    .map_err(|e| invalid(&format!("Invalid compiled dictionary word graph: {e}")))?;
+
+    let alphabet = (0..letters)
+        .map(|i| char::from_u32(u32_at(header_len + (i * 4))))
+        .collect::<Option<String>>()
+        .ok_or_else(|| invalid("Invalid compiled dictionary alphabet"))?;
+
+    Ok(Header {
+        size: WordSizeConstraint::new(to_usize(u64_at(16)), to_usize(u64_at(24))),
+        alphabet: Alphabet::new(&alphabet).map_err(|e| invalid(&e))?,
+        words: u64_at(32) as usize,
+        nodes,
+        data_offset: HEADER_WORDS + letters,
+    })
+}
+
+/// Checks every node's letters and child edges, and every edge's node number, are in range so
+/// lookups in the word graph array can't go out of bounds. `letters` must be at most
+/// MAX_LETTERS
+fn check_graph(
+    value: &dyn Fn(usize) -> u32,
+    nodes: usize,
+    edges: usize,
+    letters: usize,
+) -> Result<(), String> {
+    if nodes == 0 {
+        Err("no root node")?
+    }
+
+    // Bit 0 is the end of word marker followed by a bit for each letter
+    let beyond = !(u64::MAX >> (MAX_LETTERS - letters));
+
+    for node in 0..nodes {
+        let mask =
+            value(node * NODE_WORDS) as u64 | ((value((node * NODE_WORDS) + 1) as u64) << 32);
+        let first = value((node * NODE_WORDS) + 2) as usize;
+
+        if mask & beyond != 0 {
+            Err(format!("node {node} has letters beyond the alphabet"))?
+        }
+
+        if first + (mask & !1).count_ones() as usize > edges {
+            Err(format!("node {node} has edges out of range"))?
+        }
+    }
+
+    if let Some(edge) =
+        (0..edges).find(|&edge| value((nodes * NODE_WORDS) + edge) as usize >= nodes)
+    {
+        Err(format!("edge {edge} links to a node out of range"))?
+    }
+
+    Ok(())
+}
+
+/// Calculates the checksum of the word graph array
+pub(crate) fn checksum(data: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+
+    crc.update(data);
+
+    crc.sum()
+}
+
+/// Converts a stored length to usize, saturating on 32 bit targets
+fn to_usize(value: u64) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+/// Creates an invalid data error
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
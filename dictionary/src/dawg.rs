@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+
+use memmap2::Mmap;
+
+/// Number of u32 values per node
+pub(crate) const NODE_WORDS: usize = 3;
+
+/// Minimised directed acyclic word graph.
+/// Nodes with identical suffixes are merged and only present child links are stored.
+///
+/// The graph is stored as a single array of u32 values:
+///   nodes [nodes * 3] - for each node:
+///     letter mask (low and high 32 bits) - bit 0 is set if the node ends a word,
+///                                          bits 1-63 are set for each child letter
+///     index of the node's first child in the edges section
+///   edges [..]        - child node numbers
+pub(crate) struct Dawg {
+    nodes: usize,
+    data: DawgData,
+}
+
+/// Storage for the word graph array
+enum DawgData {
+    /// Array held in memory
+    Owned(Vec<u32>),
+    /// Array in a memory mapped file starting at a u32 offset
+    Mapped(Mmap, usize),
+}
+
+impl Dawg {
+    /// Builds a minimised word graph from a trie with nodes of a given width.
+    /// The trie is walked without recursion, adding its words in sorted order
+    pub(crate) fn from_trie(trie: &[u32], width: usize) -> Self {
+        let mut builder = DawgBuilder::new();
+        let mut word = Vec::new();
+
+        // Trie nodes on the path of the current word with the next letter to visit
+        let mut stack = vec![(0, 1)];
+
+        while let Some((elem, letter)) = stack.last_mut() {
+            if *letter == width {
+                stack.pop();
+                word.pop();
+                continue;
+            }
+
+            let child = trie[(*elem * width) + *letter] as usize;
+            word.push(*letter as u8);
+            *letter += 1;
+
+            if child == 0 {
+                word.pop();
+            } else {
+                if trie[child * width] != 0 {
+                    builder.add_word(&word);
+                }
+
+                stack.push((child, 1));
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Builds a minimised word graph from words of letter numbers in sorted order
+    pub(crate) fn from_sorted_words<'a>(words: impl Iterator<Item = &'a [u8]>) -> Self {
+        let mut builder = DawgBuilder::new();
+
+        for word in words {
+            builder.add_word(word);
+        }
+
+        builder.build()
+    }
+
+    /// Creates a word graph from a memory mapped file. The array starts at a u32 offset in the
+    /// file and must have been checked to be valid
+    pub(crate) fn from_mmap(map: Mmap, offset: usize, nodes: usize) -> Self {
+        Self {
+            nodes,
+            data: DawgData::Mapped(map, offset),
+        }
+    }
+
+    /// Returns the number of u32 values needed to store a graph
+    pub(crate) fn data_len(nodes: usize, edges: usize) -> usize {
+        (nodes * NODE_WORDS) + edges
+    }
+
+    /// Returns the number of nodes in the graph
+    pub(crate) fn node_count(&self) -> usize {
+        self.nodes
+    }
+
+    /// Returns the number of edges in the graph
+    pub(crate) fn edge_count(&self) -> usize {
+        self.data().len() - (self.nodes * NODE_WORDS)
+    }
+
+    /// Returns the used memory of the graph in bytes
+    pub(crate) fn mem_usage(&self) -> usize {
+        std::mem::size_of_val(self.data())
+    }
+
+    /// Returns the allocated memory of the graph in bytes
+    pub(crate) fn mem_alloc(&self) -> usize {
+        match &self.data {
+            DawgData::Owned(vec) => vec.capacity() * std::mem::size_of::<u32>(),
+            DawgData::Mapped(_, _) => 0,
+        }
+    }
+
+    /// Returns the graph array
+    #[inline]
+    pub(crate) fn data(&self) -> &[u32] {
+        match &self.data {
+            DawgData::Owned(vec) => vec,
+            DawgData::Mapped(map, offset) => &map_u32(map)[*offset..],
+        }
+    }
+
+    /// Determines if the node ends a word
+    #[inline]
+    pub(crate) fn ends_word(&self, elem: u32) -> bool {
+        self.data()[elem as usize * NODE_WORDS] & 1 != 0
+    }
+
+    /// Looks up the letter number in the node returning the child node or 0
+    #[inline]
+    pub(crate) fn lookup(&self, elem: u32, letter: u8) -> u32 {
+        let data = self.data();
+        let node = &data[elem as usize * NODE_WORDS..(elem as usize + 1) * NODE_WORDS];
+
+        let mask = node[0] as u64 | ((node[1] as u64) << 32);
+        let bit = 1 << letter;
+
+        if mask & bit == 0 {
+            0
+        } else {
+            // Count preceding child letters to find the edge
+            let index = (mask & (bit - 1) & !1).count_ones() as usize;
+
+            data[(self.nodes * NODE_WORDS) + node[2] as usize + index]
+        }
+    }
+}
+
+/// Returns a memory mapped file as a slice of u32 values
+#[inline]
+fn map_u32(map: &Mmap) -> &[u32] {
+    // SAFETY: memory maps are page aligned and any bit pattern is a valid u32.
+    // Files are only mapped on little endian targets (see Dictionary::open_compiled)
+    unsafe {
+        std::slice::from_raw_parts(
+            map.as_ptr() as *const u32,
+            map.len() / std::mem::size_of::<u32>(),
+        )
+    }
+}
+
+/// Builds a minimised graph from words added in sorted order (Daciuk's algorithm).
+/// Only the nodes on the path of the last word added are held unminimised. Once a word
+/// diverges from that path the nodes below the divergence can't change, so each is
+/// registered as a unique node in post order
+struct DawgBuilder {
+    /// Map of node (mask, children) to node number
+    register: HashMap<(u64, Vec<u32>), u32>,
+    /// Unique nodes in post order
+    nodes: Vec<(u64, Vec<u32>)>,
+    /// Unregistered nodes on the path of the last word, starting with the root.
+    /// The mask bit for the next node on the path is set but it isn't in the children yet
+    path: Vec<(u64, Vec<u32>)>,
+    /// The last word added
+    last: Vec<u8>,
+}
+
+impl DawgBuilder {
+    /// Creates a builder holding just the root node
+    fn new() -> Self {
+        Self {
+            register: HashMap::new(),
+            nodes: Vec::new(),
+            path: vec![(0, Vec::new())],
+            last: Vec::new(),
+        }
+    }
+
+    /// Adds a word of letter numbers. Words must be added in sorted order and repeats are ignored
+    fn add_word(&mut self, word: &[u8]) {
+        let common = word
+            .iter()
+            .zip(&self.last)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        debug_assert!(word >= self.last.as_slice());
+
+        self.register_path(common);
+
+        for &letter in &word[common..] {
+            self.path.last_mut().unwrap().0 |= 1 << letter;
+            self.path.push((0, Vec::new()));
+        }
+
+        // Mark end of word
+        self.path.last_mut().unwrap().0 |= 1;
+
+        self.last.clear();
+        self.last.extend_from_slice(word);
+    }
+
+    /// Registers the nodes on the path below a depth, linking each to its parent
+    fn register_path(&mut self, depth: usize) {
+        while self.path.len() > depth + 1 {
+            let node = self.path.pop().unwrap();
+            let num = self.register(node);
+
+            self.path.last_mut().unwrap().1.push(num);
+        }
+    }
+
+    /// Returns the node number of a node, adding it if it is unique
+    fn register(&mut self, key: (u64, Vec<u32>)) -> u32 {
+        if let Some(num) = self.register.get(&key) {
+            *num
+        } else {
+            let num = self.nodes.len() as u32;
+
+            self.nodes.push(key.clone());
+            self.register.insert(key, num);
+
+            num
+        }
+    }
+
+    /// Builds the graph. Node numbers are reversed so that the root node is node 0
+    fn build(mut self) -> Dawg {
+        self.register_path(0);
+
+        let root = self.path.pop().unwrap();
+        self.register(root);
+
+        let nodes = self.nodes.len();
+        let edges = self.nodes.iter().map(|(_, c)| c.len()).sum::<usize>();
+
+        let mut data = vec![0; (nodes * NODE_WORDS) + edges];
+        let mut next_edge = 0;
+
+        for (elem, (mask, children)) in self.nodes.into_iter().rev().enumerate() {
+            let node = &mut data[elem * NODE_WORDS..(elem + 1) * NODE_WORDS];
+
+            node[0] = mask as u32;
+            node[1] = (mask >> 32) as u32;
+            node[2] = next_edge as u32;
+
+            for c in children {
+                data[(nodes * NODE_WORDS) + next_edge] = (nodes as u32) - 1 - c;
+                next_edge += 1;
+            }
+        }
+
+        Dawg {
+            nodes,
+            data: DawgData::Owned(data),
+        }
+    }
+}
@@ -6,31 +6,172 @@ use std::fs::{read_link, symlink_metadata, File};
 use std::io::prelude::*;
 use std::io::{self, BufReader};
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use std::time::Instant;
 
 use flate2::bufread::GzDecoder;
+use memmap2::Mmap;
 use numformat::NumFormat;
 
-/// Array of element numbers of the next dictionary level
-type LetterVec = [u32; 27];
+use crate::dawg::Dawg;
+
+mod alphabet;
+mod compiled;
+mod dawg;
+
+pub use alphabet::Alphabet;
 
 /// Dictionary structure
 pub struct Dictionary {
     words: usize,
-    tree: Vec<LetterVec>,
+    size: WordSizeConstraint,
+    alphabet: Alphabet,
+    tree: Tree,
+}
+
+/// Dictionary word tree
+enum Tree {
+    /// Trie of nodes, each holding an element number of the next level for each letter
+    /// of the alphabet plus the end of word marker
+    Trie { width: usize, nodes: Vec<u32> },
+    /// Minimised directed acyclic word graph
+    Dawg(Dawg),
 }
 
 impl Dictionary {
     /// Loads a dictionary from a file
+    /// Handles gzip compressed word lists and compiled dictionaries
     pub fn new_from_file(file: &str, size: WordSizeConstraint, verbose: bool) -> io::Result<Self> {
+        Self::new_from_file_with_alphabet(file, Alphabet::english(), size, verbose)
+    }
+
+    /// Loads a dictionary with a given alphabet from a file
+    /// Handles gzip compressed word lists and compiled dictionaries
+    pub fn new_from_file_with_alphabet(
+        file: &str,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        Self::load_file(file, alphabet, size, false, verbose)
+    }
+
+    /// Loads a minimised dictionary with a given alphabet from a file.
+    /// Word lists are sorted and built straight into a minimised word graph without
+    /// creating the trie first, so peak memory is much lower than [`Dictionary::minimise`]
+    pub fn new_minimised_from_file_with_alphabet(
+        file: &str,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        Self::load_file(file, alphabet, size, true, verbose)
+    }
+
+    /// Loads a dictionary from a file, optionally minimised
+    fn load_file(
+        file: &str,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        minimise: bool,
+        verbose: bool,
+    ) -> io::Result<Self> {
         let path_buf = PathBuf::from(file);
 
         if verbose {
-            println!("Loading words from file {}", Self::file_spec(&path_buf)?);
+            eprintln!("Loading words from file {}", Self::file_spec(&path_buf)?);
         }
 
         // Create buf reader for the file
-        Self::new_from_bufread(&mut BufReader::new(File::open(&path_buf)?), size, verbose)
+        let mut bufread = BufReader::new(File::open(&path_buf)?);
+
+        // Check for compiled dictionary signature
+        if compiled::is_compiled(bufread.fill_buf()?) {
+            let dictionary = Self::open_compiled(file, verbose)?;
+
+            if dictionary.size != size {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Compiled dictionary was built with word sizes {}, {} requested",
+                        dictionary.size, size
+                    ),
+                ))?
+            }
+
+            if dictionary.alphabet != alphabet {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Compiled dictionary was built with alphabet {}, {} requested",
+                        dictionary.alphabet, alphabet
+                    ),
+                ))?
+            }
+
+            return Ok(dictionary);
+        }
+
+        Self::load_bufread(&mut bufread, alphabet, size, minimise, verbose)
+    }
+
+    /// Opens a compiled dictionary file created with save_compiled.
+    /// The file is memory mapped and used in place
+    pub fn open_compiled(file: &str, verbose: bool) -> io::Result<Self> {
+        if cfg!(target_endian = "big") {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Compiled dictionaries are not supported on big endian targets",
+            ))?
+        }
+
+        // Get start time
+        let start_time = Instant::now();
+
+        // SAFETY: the file must not be modified while it is mapped
+        let map = unsafe { Mmap::map(&File::open(file)?)? };
+
+        let header = compiled::read_header(&map)?;
+
+        let dictionary = Self {
+            words: header.words,
+            size: header.size,
+            alphabet: header.alphabet,
+            tree: Tree::Dawg(Dawg::from_mmap(map, header.data_offset, header.nodes)),
+        };
+
+        if verbose {
+            eprintln!(
+                "Compiled dictionary (word sizes {}) mapped in {} seconds",
+                dictionary.size,
+                start_time.elapsed().as_secs_f64().num_format_sigdig(2)
+            );
+
+            dictionary.print_stats();
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Saves the dictionary to a compiled dictionary file which can be opened with
+    /// open_compiled or new_from_file. The dictionary is saved in minimised form
+    pub fn save_compiled(&self, file: &str) -> io::Result<()> {
+        self.with_dawg(|dawg| compiled::write(file, &self.size, &self.alphabet, self.words, dawg))
+    }
+
+    /// Returns a checksum of the dictionary's alphabet and words, the same as the one stored
+    /// in a compiled dictionary file. Dictionaries with the same alphabet and words have the
+    /// same checksum whether they are minimised or not
+    pub fn checksum(&self) -> u32 {
+        self.with_dawg(|dawg| compiled::checksum(&compiled::data_bytes(&self.alphabet, dawg)))
+    }
+
+    /// Calls a function with the minimised word graph, building it if needed
+    fn with_dawg<R>(&self, f: impl FnOnce(&Dawg) -> R) -> R {
+        match &self.tree {
+            Tree::Trie { width, nodes } => f(&Dawg::from_trie(nodes, *width)),
+            Tree::Dawg(dawg) => f(dawg),
+        }
     }
 
     /// Loads a dictionary from a string
@@ -41,7 +182,7 @@ impl Dictionary {
         verbose: bool,
     ) -> io::Result<Self> {
         if verbose {
-            println!("Loading words from string '{string}'");
+            eprintln!("Loading words from string '{string}'");
         }
 
         Self::new_from_bufread(&mut BufReader::new(string.as_bytes()), size, verbose)
@@ -55,18 +196,70 @@ impl Dictionary {
         verbose: bool,
     ) -> io::Result<Self> {
         if verbose {
-            println!("Loading words from byte array (length {})", bytes.len());
+            eprintln!("Loading words from byte array (length {})", bytes.len());
         }
 
         Self::new_from_bufread(&mut BufReader::new(bytes), size, verbose)
     }
 
+    /// Loads a dictionary with a given alphabet from a string
+    pub fn new_from_string_with_alphabet(
+        string: &str,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        if verbose {
+            eprintln!("Loading words from string '{string}'");
+        }
+
+        Self::new_from_bufread_with_alphabet(
+            &mut BufReader::new(string.as_bytes()),
+            alphabet,
+            size,
+            verbose,
+        )
+    }
+
     /// Loads a dictionary from an entity implementing BufRead
     /// Handles gzip compressed buffers
     pub fn new_from_bufread(
         bufread: &mut dyn BufRead,
         size: WordSizeConstraint,
         verbose: bool,
+    ) -> io::Result<Self> {
+        Self::new_from_bufread_with_alphabet(bufread, Alphabet::english(), size, verbose)
+    }
+
+    /// Loads a dictionary with a given alphabet from an entity implementing BufRead
+    /// Handles gzip compressed buffers
+    pub fn new_from_bufread_with_alphabet(
+        bufread: &mut dyn BufRead,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        Self::load_bufread(bufread, alphabet, size, false, verbose)
+    }
+
+    /// Loads a minimised dictionary with a given alphabet from an entity implementing BufRead
+    /// Handles gzip compressed buffers. See [`Dictionary::new_minimised_from_file_with_alphabet`]
+    pub fn new_minimised_from_bufread_with_alphabet(
+        bufread: &mut dyn BufRead,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        verbose: bool,
+    ) -> io::Result<Self> {
+        Self::load_bufread(bufread, alphabet, size, true, verbose)
+    }
+
+    /// Loads a dictionary from an entity implementing BufRead, optionally minimised
+    fn load_bufread(
+        bufread: &mut dyn BufRead,
+        alphabet: Alphabet,
+        size: WordSizeConstraint,
+        minimise: bool,
+        verbose: bool,
     ) -> io::Result<Self> {
         // Get start time
         let start_time = Instant::now();
@@ -78,30 +271,42 @@ impl Dictionary {
         if buf.len() >= 2 && buf[0] == 0x1f && buf[1] == 0x8b {
             // gzip compressed file
             if verbose {
-                println!("Decompressing word list");
+                eprintln!("Decompressing word list");
             }
 
             Self::new_from_bufread_internal(
                 start_time,
                 &mut BufReader::new(GzDecoder::new(bufread)),
+                alphabet,
                 size,
+                minimise,
                 verbose,
             )
         } else {
-            Self::new_from_bufread_internal(start_time, bufread, size, verbose)
+            Self::new_from_bufread_internal(start_time, bufread, alphabet, size, minimise, verbose)
         }
     }
 
-    /// Loads a dictionary from an entity implementing BufRead
+    /// Loads a dictionary from an entity implementing BufRead.
+    /// When minimising, the words are collected, sorted and built straight into a word graph
     fn new_from_bufread_internal(
         start_time: Instant,
         bufread: &mut dyn BufRead,
+        alphabet: Alphabet,
         size: WordSizeConstraint,
+        minimise: bool,
         verbose: bool,
     ) -> io::Result<Self> {
-        let mut tree = Vec::new();
+        // Each node has an entry for each letter plus the end of word marker
+        let width = alphabet.len() + 1;
+
+        let mut tree = if minimise { Vec::new() } else { vec![0; width] };
+
+        // Letters of each word and the start of each word in them when minimising
+        let mut word_letters: Vec<u8> = Vec::new();
+        let mut word_starts: Vec<u32> = Vec::new();
 
-        let empty = [0; 27];
+        let mut letters = Vec::new();
 
         let mut lines: usize = 0;
         let mut words: usize = 0;
@@ -109,8 +314,6 @@ impl Dictionary {
         let mut too_long: usize = 0;
         let mut wrong_case: usize = 0;
 
-        tree.push(empty);
-
         // Iterate file lines
         for line in bufread.lines() {
             let line = line?;
@@ -118,7 +321,7 @@ impl Dictionary {
             lines += 1;
 
             // Check length
-            let length = line.len();
+            let length = line.chars().count();
 
             if length > size.max {
                 too_long += 1;
@@ -130,116 +333,231 @@ impl Dictionary {
                 continue;
             }
 
-            // Make sure word consists of all lower case ascii characters
-            if !Self::is_ascii_lower(&line) {
+            // Make sure word consists of all lower case alphabet letters
+            letters.clear();
+
+            let valid = line.chars().all(|c| match alphabet.lchar_to_elem(c) {
+                Some(e) => {
+                    letters.push(e as usize);
+                    true
+                }
+                None => false,
+            });
+
+            if !valid {
                 wrong_case += 1;
                 continue;
             }
 
-            // Add this word to the tree
             words += 1;
 
+            if minimise {
+                // Save the word for sorting
+                word_starts.push(word_letters.len() as u32);
+                word_letters.extend(letters.iter().map(|&l| l as u8));
+                continue;
+            }
+
+            // Add this word to the tree
             let mut cur_elem = 0;
 
-            for c in line.chars() {
-                let letter: usize = Dictionary::lchar_to_elem(c);
+            for &letter in &letters {
+                let slot = (cur_elem * width) + letter;
 
-                cur_elem = if tree[cur_elem][letter] == 0 {
-                    tree.push(empty);
-                    let e = tree.len() - 1;
-                    tree[cur_elem][letter] = e as u32;
+                cur_elem = if tree[slot] == 0 {
+                    let e = tree.len() / width;
+                    tree.resize(tree.len() + width, 0);
+                    tree[slot] = e as u32;
                     e
                 } else {
-                    tree[cur_elem][letter] as usize
+                    tree[slot] as usize
                 };
             }
 
             // Mark end of word
-            tree[cur_elem][0] = 1;
+            tree[cur_elem * width] = 1;
         }
 
-        let dictionary = Self { words, tree };
+        let tree = if minimise {
+            word_starts.push(word_letters.len() as u32);
+
+            let word =
+                |i: usize| &word_letters[word_starts[i] as usize..word_starts[i + 1] as usize];
+
+            let mut order = (0..word_starts.len() - 1).collect::<Vec<_>>();
+            order.sort_unstable_by(|&a, &b| word(a).cmp(word(b)));
+
+            Tree::Dawg(Dawg::from_sorted_words(order.into_iter().map(word)))
+        } else {
+            Tree::Trie { width, nodes: tree }
+        };
+
+        let dictionary = Self {
+            words,
+            size,
+            alphabet,
+            tree,
+        };
 
         if verbose {
-            println!(
+            eprintln!(
                 "Dictionary read in {} seconds",
                 start_time.elapsed().as_secs_f64().num_format_sigdig(2)
             );
 
-            println!(
-                "{} total words, ({} too short, {} too long, {} not all lower case)",
+            eprintln!(
+                "{} total words, ({} too short, {} too long, {} not all lower case alphabet letters)",
                 lines.num_format(),
                 too_short.num_format(),
                 too_long.num_format(),
                 wrong_case.num_format()
             );
 
-            println!(
-                "Dictionary words {}, tree nodes {} ({} bytes of {} allocated)",
-                dictionary.word_count().num_format(),
-                dictionary.tree_node_count().num_format(),
-                dictionary.tree_mem_usage().num_format(),
-                dictionary.tree_mem_alloc().num_format(),
-            );
+            dictionary.print_stats();
         }
 
         Ok(dictionary)
     }
 
+    /// Converts the dictionary tree to a minimised directed acyclic word graph (DAWG).
+    /// Shared word suffixes are merged and only present letters are stored for each node,
+    /// greatly reducing the memory used by the dictionary. The full trie has already been
+    /// built, so this doesn't lower peak memory use; load with
+    /// [`Dictionary::new_minimised_from_file_with_alphabet`] for that
+    pub fn minimise(&mut self, verbose: bool) {
+        if let Tree::Trie { width, nodes } = &self.tree {
+            let start_time = Instant::now();
+
+            self.tree = Tree::Dawg(Dawg::from_trie(nodes, *width));
+
+            if verbose {
+                eprintln!(
+                    "Dictionary minimised in {} seconds",
+                    start_time.elapsed().as_secs_f64().num_format_sigdig(2)
+                );
+
+                self.print_stats();
+            }
+        }
+    }
+
+    /// Returns true if the dictionary tree has been minimised
+    pub fn is_minimised(&self) -> bool {
+        matches!(self.tree, Tree::Dawg(_))
+    }
+
+    /// Prints dictionary statistics
+    fn print_stats(&self) {
+        eprintln!(
+            "Dictionary words {}, tree nodes {} ({} bytes of {} allocated)",
+            self.word_count().num_format(),
+            self.tree_node_count().num_format(),
+            self.tree_mem_usage().num_format(),
+            self.tree_mem_alloc().num_format(),
+        );
+    }
+
     /// Returns the number of words stored in the dictionary
     pub fn word_count(&self) -> usize {
         self.words
     }
 
+    /// Returns the word size constraints the dictionary was built with
+    pub fn size_constraint(&self) -> &WordSizeConstraint {
+        &self.size
+    }
+
+    /// Returns the alphabet of the dictionary
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
     /// Returns the size of the dictionary tree
     pub fn tree_node_count(&self) -> usize {
-        self.tree.len()
+        match &self.tree {
+            Tree::Trie { width, nodes } => nodes.len() / width,
+            Tree::Dawg(dawg) => dawg.node_count(),
+        }
     }
 
     /// Returns the used memory of the dictionary tree in bytes
     pub fn tree_mem_usage(&self) -> usize {
-        self.tree_node_count() * std::mem::size_of::<LetterVec>()
+        match &self.tree {
+            Tree::Trie { nodes, .. } => std::mem::size_of_val(nodes.as_slice()),
+            Tree::Dawg(dawg) => dawg.mem_usage(),
+        }
     }
 
     /// Returns the allocated memory of the dictionary tree in bytes
     pub fn tree_mem_alloc(&self) -> usize {
-        self.tree.capacity() * std::mem::size_of::<LetterVec>()
+        match &self.tree {
+            Tree::Trie { nodes, .. } => nodes.capacity() * std::mem::size_of::<u32>(),
+            Tree::Dawg(dawg) => dawg.mem_alloc(),
+        }
+    }
+
+    /// Determines if a word in either case is in the dictionary
+    pub fn contains_word(&self, word: &str) -> bool {
+        let mut elem = 0;
+
+        for c in word.chars() {
+            let Some(letter) = self.alphabet.char_to_elem(c) else {
+                return false;
+            };
+
+            elem = self.lookup_elem_letter_num(elem, letter);
+
+            if elem == 0 {
+                return false;
+            }
+        }
+
+        elem != 0 && self.elem_ends_word(elem)
     }
 
     /// Determines if the letter ends the word
     #[inline]
     pub fn elem_ends_word(&self, elem: u32) -> bool {
-        self.tree[elem as usize][0] != 0
+        match &self.tree {
+            Tree::Trie { width, nodes } => nodes[elem as usize * width] != 0,
+            Tree::Dawg(dawg) => dawg.ends_word(elem),
+        }
     }
 
-    /// Looks up the letter number (1-26) in the dictionary tree node
+    /// Looks up the letter number (1 to the alphabet length) in the dictionary tree node.
+    /// Letter numbers beyond the alphabet are never found
     #[inline]
     pub fn lookup_elem_letter_num(&self, elem: u32, letter: u8) -> u32 {
-        self.tree[elem as usize][letter as usize]
-    }
-
-    #[inline]
-    /// Converts a lower case character into a dictionary entry index (usize)
-    fn lchar_to_elem<T>(c: char) -> T
-    where
-        T: std::convert::From<u8>,
-    {
-        (c as u8 - (b'a' - 1)).into()
+        match &self.tree {
+            Tree::Trie { width, nodes } => {
+                if (letter as usize) < *width {
+                    nodes[(elem as usize * width) + letter as usize]
+                } else {
+                    0
+                }
+            }
+            Tree::Dawg(dawg) => dawg.lookup(elem, letter),
+        }
     }
 
     #[inline]
-    /// Converts an upper case character into a dictionary entry index (u8)
+    #[deprecated(note = "use alphabet().char_to_elem() which handles the dictionary's alphabet")]
+    /// Converts an upper case English letter into a dictionary entry index (u8)
     pub fn uchar_to_elem<T>(c: char) -> T
     where
         T: std::convert::From<u8>,
     {
-        (c as u8 - (b'A' - 1)).into()
+        english()
+            .char_to_elem(c)
+            .expect("Letter should be in the English alphabet")
+            .into()
     }
 
     #[inline]
-    /// Converts dictionary entry index (u8) into an upper case character
+    #[deprecated(note = "use alphabet().elem_to_uchar() which handles the dictionary's alphabet")]
+    /// Converts dictionary entry index (u8) into an upper case English letter
     pub fn elem_to_uchar(e: u8) -> char {
-        (e + b'A' - 1) as char
+        english().elem_to_uchar(e)
     }
 
     /// Follows symlinks in a path returning the followed paths as a string
@@ -258,21 +576,38 @@ impl Dictionary {
             Ok(format!("{}", path.to_string_lossy()))
         }
     }
+}
 
-    #[inline]
-    /// Returns true if the passed string is all lower case
-    fn is_ascii_lower(s: &str) -> bool {
-        s.chars().all(|c| c.is_ascii_lowercase())
-    }
+/// Returns the English alphabet used by the deprecated letter conversions
+fn english() -> &'static Alphabet {
+    static ENGLISH: OnceLock<Alphabet> = OnceLock::new();
+
+    ENGLISH.get_or_init(Alphabet::english)
 }
 
 /// Word size constraints to use when loading a dictionary
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct WordSizeConstraint {
     min: usize,
     max: usize,
 }
 
 impl WordSizeConstraint {
+    /// Creates word size constraints with a minimum and maximum word length
+    pub fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns the minimum length for a word
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Returns the maximum length for a word
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
     /// Sets the minimum length for a word
     pub fn set_min(&mut self, min: usize) {
         self.min = min;
@@ -284,6 +619,16 @@ impl WordSizeConstraint {
     }
 }
 
+impl std::fmt::Display for WordSizeConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.max == usize::MAX {
+            write!(f, "{}+", self.min)
+        } else {
+            write!(f, "{}-{}", self.min, self.max)
+        }
+    }
+}
+
 impl Default for WordSizeConstraint {
     fn default() -> Self {
         Self {
@@ -294,11 +639,13 @@ impl Default for WordSizeConstraint {
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use flate2::write::GzEncoder;
     use flate2::Compression;
 
     use super::*;
+    use crate::alphabet::MAX_LETTERS;
 
     fn gz_dict(string: &str) -> Vec<u8> {
         let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
@@ -323,6 +670,21 @@ mod tests {
         test_dict1(dictionary)
     }
 
+    #[test]
+    fn dict1m() {
+        // Create minimised dictionary with one word in it "rust"
+        let mut dictionary =
+            Dictionary::new_from_string("rust", Default::default(), false).unwrap();
+
+        dictionary.minimise(false);
+
+        assert!(dictionary.is_minimised());
+        assert_eq!(dictionary.tree_node_count(), 5);
+        assert_eq!(dictionary.tree_mem_usage(), ((5 * 3) + 4) * 4);
+
+        test_dict_words(&dictionary, &["rust"], &["rus", "rusty", "trust"]);
+    }
+
     fn test_dict1(dictionary: Dictionary) {
         assert_eq!(dictionary.word_count(), 1);
         assert_eq!(dictionary.tree_node_count(), 5);
@@ -365,6 +727,250 @@ mod tests {
         test_dict2(dictionary);
     }
 
+    #[test]
+    fn dict2m() {
+        // Create minimised dictionary with two words, "rust" and "rusty"
+        let mut dictionary =
+            Dictionary::new_from_string("rust\nrusty", Default::default(), false).unwrap();
+
+        dictionary.minimise(false);
+
+        assert_eq!(dictionary.tree_node_count(), 6);
+
+        test_dict_words(&dictionary, &["rust", "rusty"], &["rus", "rustys", "rut"]);
+    }
+
+    #[test]
+    fn dict_suffix() {
+        // Create minimised dictionary with shared suffixes
+        let mut dictionary =
+            Dictionary::new_from_string("cats\nrats\nrat\ncat\nbat", Default::default(), false)
+                .unwrap();
+
+        assert_eq!(dictionary.tree_node_count(), 12);
+
+        dictionary.minimise(false);
+
+        // Root, c/r, ca/ra, cat/rat, b, ba, and cats/rats/bat sharing the word end leaf
+        assert_eq!(dictionary.tree_node_count(), 7);
+
+        test_dict_words(
+            &dictionary,
+            &["cats", "rats", "rat", "cat", "bat"],
+            &["bats", "ca", "at", "rast"],
+        );
+    }
+
+    #[test]
+    fn dict_suffix_sorted() {
+        // Load the unsorted words straight into a minimised dictionary, with a repeated word
+        let words = "cats\nrats\nrat\ncat\nbat\nrat";
+
+        let dictionary = Dictionary::new_minimised_from_bufread_with_alphabet(
+            &mut BufReader::new(&gz_dict(words)[..]),
+            Alphabet::english(),
+            Default::default(),
+            false,
+        )
+        .unwrap();
+
+        assert!(dictionary.is_minimised());
+        assert_eq!(dictionary.word_count(), 6);
+        assert_eq!(dictionary.tree_node_count(), 7);
+
+        test_dict_words(
+            &dictionary,
+            &["cats", "rats", "rat", "cat", "bat"],
+            &["bats", "ca", "at", "rast"],
+        );
+
+        // The graph matches the one built from the trie
+        let mut trie = Dictionary::new_from_string(words, Default::default(), false).unwrap();
+        trie.minimise(false);
+
+        match (&dictionary.tree, &trie.tree) {
+            (Tree::Dawg(a), Tree::Dawg(b)) => assert_eq!(a.data(), b.data()),
+            _ => panic!("Not minimised"),
+        }
+    }
+
+    #[test]
+    fn compiled() {
+        let file = std::env::temp_dir().join(format!("boggle_test_{}.dict", std::process::id()));
+        let file = file.to_str().unwrap();
+
+        let mut size = WordSizeConstraint::default();
+        size.set_min(3);
+
+        let words = "cats\nrats\nrat\ncat\nbat\nat";
+
+        let dictionary = Dictionary::new_from_string(words, size, false).unwrap();
+        dictionary.save_compiled(file).unwrap();
+
+        // Open the compiled file directly
+        let compiled = Dictionary::open_compiled(file, false).unwrap();
+
+        assert!(compiled.is_minimised());
+        assert_eq!(compiled.word_count(), 5);
+        assert_eq!(compiled.size_constraint(), &size);
+        assert_eq!(compiled.tree_node_count(), 7);
+
+        test_dict_words(
+            &compiled,
+            &["cats", "rats", "rat", "cat", "bat"],
+            &["bats", "ca", "at", "rast"],
+        );
+
+        assert_eq!(compiled.checksum(), dictionary.checksum());
+
+        // Open the compiled file as a word list
+        assert!(Dictionary::new_from_file(file, size, false).is_ok());
+        assert!(Dictionary::new_from_file(file, Default::default(), false).is_err());
+
+        // Corrupt the file
+        let mut bytes = std::fs::read(file).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(file, bytes).unwrap();
+
+        assert!(Dictionary::open_compiled(file, false).is_err());
+
+        // Link the last edge to a node out of range with a valid checksum
+        let mut bytes = std::fs::read(file).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        let len = bytes.len();
+        bytes[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        let crc = compiled::checksum(&bytes[56..]);
+        bytes[48..52].copy_from_slice(&crc.to_le_bytes());
+        std::fs::write(file, bytes).unwrap();
+
+        let err = Dictionary::open_compiled(file, false).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("out of range"));
+
+        // Letters beyond the largest alphabet are rejected before the word graph is read
+        let mut bytes = std::fs::read(file).unwrap();
+        bytes[52..56].copy_from_slice(&64u32.to_le_bytes());
+        std::fs::write(file, bytes).unwrap();
+
+        let err = Dictionary::open_compiled(file, false).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("64 letters"));
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn compiled_max_letters() {
+        let file =
+            std::env::temp_dir().join(format!("boggle_test_{}_max.dict", std::process::id()));
+        let file = file.to_str().unwrap();
+
+        // English, Cyrillic А to Я and Greek Α to Ε make the largest alphabet
+        let letters = ('A'..='Z')
+            .chain('А'..='Я')
+            .chain('Α'..='Ε')
+            .collect::<String>();
+        let alphabet = Alphabet::new(&letters).unwrap();
+        assert_eq!(alphabet.len(), MAX_LETTERS);
+
+        // The last letter uses the top bit of the node letter masks
+        let dictionary = Dictionary::new_from_string_with_alphabet(
+            "abε\nεa\nяε",
+            alphabet.clone(),
+            Default::default(),
+            false,
+        )
+        .unwrap();
+        dictionary.save_compiled(file).unwrap();
+
+        let compiled = Dictionary::open_compiled(file, false).unwrap();
+
+        assert_eq!(compiled.alphabet(), &alphabet);
+        assert_eq!(compiled.word_count(), 3);
+        test_dict_words(&compiled, &["abε", "εa", "яε"], &["ab", "ε", "aε"]);
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn contains_word() {
+        let mut dictionary =
+            Dictionary::new_from_string("rust\nrusty", Default::default(), false).unwrap();
+
+        for _ in 0..2 {
+            assert!(dictionary.contains_word("rust"));
+            assert!(dictionary.contains_word("RUSTY"));
+            assert!(!dictionary.contains_word("rus"));
+            assert!(!dictionary.contains_word("trust"));
+            assert!(!dictionary.contains_word("rüst"));
+            assert!(!dictionary.contains_word(""));
+
+            dictionary.minimise(false);
+        }
+    }
+
+    #[test]
+    fn unicode() {
+        let file = std::env::temp_dir().join(format!("boggle_test_{}_de.dict", std::process::id()));
+        let file = file.to_str().unwrap();
+
+        let german = Alphabet::named("german").unwrap();
+        let words = "bär\nbären\nöl\nüber\nstraße\nbar";
+
+        let mut size = WordSizeConstraint::default();
+        size.set_min(3);
+
+        let dictionary =
+            Dictionary::new_from_string_with_alphabet(words, german.clone(), size, false).unwrap();
+
+        // Two letter "öl" too short, "straße" has a letter not in the alphabet
+        assert_eq!(dictionary.word_count(), 4);
+        assert_eq!(dictionary.tree_mem_usage() % (30 * 4), 0);
+
+        test_dict_words(
+            &dictionary,
+            &["bär", "bären", "über", "bar"],
+            &["bä", "übe"],
+        );
+
+        // Compiled dictionaries keep the alphabet
+        dictionary.save_compiled(file).unwrap();
+
+        let compiled = Dictionary::open_compiled(file, false).unwrap();
+
+        assert_eq!(compiled.alphabet(), &german);
+        test_dict_words(&compiled, &["bär", "bären", "über", "bar"], &["bä", "übe"]);
+
+        assert!(Dictionary::new_from_file_with_alphabet(file, german, size, false).is_ok());
+        assert!(Dictionary::new_from_file(file, size, false).is_err());
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    fn test_dict_words(dictionary: &Dictionary, present: &[&str], absent: &[&str]) {
+        let lookup = |word: &str| {
+            word.chars().try_fold(0, |elem, c| {
+                let letter = dictionary.alphabet().lchar_to_elem(c).unwrap();
+
+                match dictionary.lookup_elem_letter_num(elem, letter) {
+                    0 => None,
+                    next => Some(next),
+                }
+            })
+        };
+
+        for word in present {
+            assert!(dictionary.elem_ends_word(lookup(word).unwrap()), "{word}");
+        }
+
+        for word in absent {
+            assert!(
+                !lookup(word).is_some_and(|e| dictionary.elem_ends_word(e)),
+                "{word}"
+            );
+        }
+    }
+
     fn test_dict2(dictionary: Dictionary) {
         assert_eq!(dictionary.word_count(), 2);
         assert_eq!(dictionary.tree_node_count(), 6);
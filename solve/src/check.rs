@@ -0,0 +1,58 @@
+use board::Board;
+use solver::{find_word, ScoreRules, SolverArgs, WordNotFound};
+
+use crate::results::{path_string, ShowPaths};
+
+/// Searches the board for a single word and prints each path found marked on the board, or
+/// the reason the word can't be made
+pub fn check(args: SolverArgs, rules: &ScoreRules, word: &str) -> Result<(), WordNotFound> {
+    let board = args.board;
+
+    let show_paths = if board.depth() > 1 {
+        ShowPaths::Layered
+    } else {
+        ShowPaths::Flat
+    };
+
+    let found = find_word(args, rules, word)?;
+
+    let score = rules.found_score(&found);
+
+    println!(
+        "{} is on the board ({score} {}):",
+        found.word,
+        if score == 1 { "point" } else { "points" }
+    );
+
+    let no_choices = Vec::new();
+
+    for (i, path) in found.paths.iter().enumerate() {
+        let choices = found.choices.get(i).unwrap_or(&no_choices);
+
+        match found.scores.get(i) {
+            Some(score) => println!("{} = {score}", path_string(path, choices, show_paths)),
+            None => println!("{}", path_string(path, choices, show_paths)),
+        }
+
+        print_path(board, path);
+    }
+
+    Ok(())
+}
+
+/// Prints the board with each cell on the path marked with its step number
+fn print_path(board: &Board, path: &[(usize, usize, usize)]) {
+    let steps = path
+        .iter()
+        .map(|&(x, y, z)| board.cell_at_layer(x, y, z))
+        .collect::<Vec<_>>();
+
+    board.print_cells(|cell| {
+        let face = board.cell_face(cell).to_string();
+
+        match steps.iter().position(|&step| step == Some(cell)) {
+            Some(step) => format!("{face}{}", step + 1),
+            None => face.to_lowercase(),
+        }
+    });
+}
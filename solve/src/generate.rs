@@ -0,0 +1,66 @@
+use numformat::NumFormat;
+use solver::{generate_boards, GenerateArgs, GenerateStats, GeneratedBoard, ScoreRules};
+
+/// Generates boards meeting constraints and prints them with the acceptance statistics
+pub fn generate(args: GenerateArgs) -> Result<(), String> {
+    let (boards, stats) = generate_boards(args)?;
+
+    for (i, generated) in boards.iter().enumerate() {
+        print_board(i + 1, generated);
+        println!();
+    }
+
+    print_stats(&stats);
+
+    Ok(())
+}
+
+/// Prints a generated board with a summary of its words
+fn print_board(number: usize, generated: &GeneratedBoard) {
+    let board = &generated.board;
+    let rules = ScoreRules::for_board(board);
+
+    let longest = generated
+        .words
+        .iter()
+        .map(|f| f.word.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!("Board {number} (seed {}):", generated.seed);
+    board.print();
+
+    println!(
+        "{} {} found, maximum score {}, longest word {} letters",
+        generated.words.len().num_format(),
+        if generated.words.len() == 1 {
+            "word"
+        } else {
+            "words"
+        },
+        rules.total_score(&generated.words).num_format(),
+        longest
+    );
+}
+
+/// Prints the generator acceptance statistics
+fn print_stats(stats: &GenerateStats) {
+    println!(
+        "Accepted {} of {} boards ({}%)",
+        stats.accepted.num_format(),
+        stats.attempts.num_format(),
+        (stats.acceptance_rate() * 100.0).num_format_sigdig(2)
+    );
+
+    if !stats.rejections.is_empty() {
+        println!(
+            "Rejected: {}",
+            stats
+                .rejections
+                .iter()
+                .map(|(rejection, count)| format!("{rejection} {}", count.num_format()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
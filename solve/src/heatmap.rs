@@ -0,0 +1,130 @@
+use board::Board;
+use solver::CellUsage;
+
+/// Shades for cells from least to most used
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Prints the number of words and points passing through each cell of the board as shaded
+/// grids. Dead cells which no word passes through are unshaded
+pub fn print_heat_map(board: &Board, usage: &[CellUsage]) {
+    println!("Words through each cell:");
+    print_grid(board, &usage.iter().map(|u| u.words).collect::<Vec<_>>());
+
+    println!("Points through each cell:");
+    print_grid(
+        board,
+        &usage.iter().map(|u| u.score as usize).collect::<Vec<_>>(),
+    );
+
+    let dead = usage.iter().filter(|u| u.words == 0).count();
+
+    println!(
+        "{dead} dead {} of {}",
+        if dead == 1 { "cell" } else { "cells" },
+        usage.len()
+    );
+}
+
+/// Prints each cell's face followed by its value laid out as the board
+fn print_grid(board: &Board, values: &[usize]) {
+    let strings = cell_strings(board, values);
+
+    board.print_cells(|cell| strings[cell].clone());
+}
+
+/// Returns each cell's face followed by its value, shaded by the value relative to the
+/// largest value, indexed by cell number
+fn cell_strings(board: &Board, values: &[usize]) -> Vec<String> {
+    let max = values.iter().copied().max().unwrap_or(0);
+    let value_len = max.to_string().len();
+
+    let face_len = (0..board.cell_count())
+        .map(|cell| board.cell_face(cell).to_string().chars().count())
+        .max()
+        .unwrap_or(1);
+
+    (0..board.cell_count())
+        .map(|cell| {
+            let value = values[cell];
+            let shade = shade(value, max);
+
+            format!(
+                "{:<face_len$} {shade}{shade} {value:>value_len$}",
+                board.cell_face(cell).to_string()
+            )
+        })
+        .collect()
+}
+
+/// Returns the shade for a value. Zero is unshaded, otherwise the value is shaded by its
+/// quarter of the largest value
+fn shade(value: usize, max: usize) -> char {
+    if value == 0 {
+        SHADES[0]
+    } else {
+        SHADES[(value * (SHADES.len() - 1)).div_ceil(max)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::Alphabet;
+
+    use super::*;
+
+    #[test]
+    fn shades() {
+        // Zero is unshaded, otherwise each quarter of the largest value is a shade darker
+        assert_eq!(shade(0, 0), ' ');
+        assert_eq!(shade(0, 8), ' ');
+        assert_eq!(shade(1, 8), '░');
+        assert_eq!(shade(2, 8), '░');
+        assert_eq!(shade(3, 8), '▒');
+        assert_eq!(shade(4, 8), '▒');
+        assert_eq!(shade(5, 8), '▓');
+        assert_eq!(shade(7, 8), '█');
+        assert_eq!(shade(8, 8), '█');
+        assert_eq!(shade(1, 1), '█');
+    }
+
+    #[test]
+    fn strings() {
+        // Faces and values are padded to the longest
+        let board =
+            Board::new(None, None, ["A", "QU", "B", "C"].map(String::from).to_vec()).unwrap();
+
+        assert_eq!(
+            cell_strings(&board, &[0, 12, 3, 6]),
+            vec!["A      0", "QU ██ 12", "B  ░░  3", "C  ▒▒  6"]
+        );
+
+        // Nothing is found on an empty board
+        assert_eq!(
+            cell_strings(&board, &[0, 0, 0, 0]),
+            vec!["A     0", "QU    0", "B     0", "C     0"]
+        );
+    }
+
+    #[test]
+    fn shaped() {
+        // Layouts with holes only have strings for their cells
+        let board = Board::from_layout_text(". A\nB C", &Alphabet::english()).unwrap();
+
+        assert_eq!(
+            cell_strings(&board, &[1, 2, 4]),
+            vec!["A ░░ 1", "B ▒▒ 2", "C ██ 4"]
+        );
+
+        // 3D boards have strings for the cells of every layer
+        let board = Board::new_with_depth(
+            Some(1),
+            Some(1),
+            2,
+            ["A", "B"].map(String::from).to_vec(),
+            &Alphabet::english(),
+        )
+        .unwrap();
+
+        assert_eq!(cell_strings(&board, &[2, 0]), vec!["A ██ 2", "B    0"]);
+    }
+}
@@ -0,0 +1,310 @@
+use board::{Board, DiceFace};
+use dictionary::Alphabet;
+use serde_json::{json, Value};
+use solver::{CellUsage, Difficulty, FoundWord, ScoreRules};
+
+/// Details of the solve to output as JSON
+pub struct JsonDetails<'a> {
+    /// The board solved
+    pub board: &'a Board,
+    /// Game type name if the board was randomly generated
+    pub game_type: Option<String>,
+    /// Seed used if the board was randomly generated
+    pub seed: Option<u64>,
+    /// Dictionary file used
+    pub dictionary_file: &'a str,
+    /// Number of words in the dictionary
+    pub dictionary_words: usize,
+    /// Dictionary alphabet
+    pub alphabet: &'a Alphabet,
+    /// Probability of the board being rolled with the game type's dice if requested
+    pub probability: Option<f64>,
+    /// Scoring rules
+    pub rules: &'a ScoreRules,
+    /// Difficulty rating of the board if requested
+    pub difficulty: Option<&'a Difficulty>,
+    /// Words and points through each cell if requested
+    pub heat_map: Option<&'a [CellUsage]>,
+}
+
+/// Prints the solver results as a JSON document
+pub fn print_results_json(found: Vec<FoundWord>, details: JsonDetails) {
+    println!("{:#}", results_json(found, details));
+}
+
+/// Converts the solver results to a JSON document
+fn results_json(mut found: Vec<FoundWord>, details: JsonDetails) -> Value {
+    // Sort words by longest first then alphabetical
+    found.sort_by(|a, b| {
+        b.word
+            .chars()
+            .count()
+            .cmp(&a.word.chars().count())
+            .then_with(|| a.word.cmp(&b.word))
+    });
+
+    let (width, height) = details.board.dimension();
+    let depth = details.board.depth();
+
+    let faces = grid_json(details.board, |cell| {
+        face_json(details.board.cell_face(cell))
+    });
+
+    let words = found
+        .iter()
+        .map(|f| {
+            let mut word = json!({
+                "word": f.word,
+                "length": f.word.chars().count(),
+                "score": details.rules.found_score(f),
+                "paths": f
+                    .paths
+                    .iter()
+                    .map(|path| {
+                        path.iter()
+                            .map(|&(x, y, z)| {
+                                if depth == 1 {
+                                    json!([x, y])
+                                } else {
+                                    json!([x, y, z])
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>(),
+            });
+
+            // Letters chosen for wildcard faces and readings used for alternatives faces for each
+            // path
+            if f.choices.iter().any(|c| !c.is_empty()) {
+                word["choices"] = f
+                    .choices
+                    .iter()
+                    .map(|choices| {
+                        choices
+                            .iter()
+                            .map(|(step, reading)| json!({ "step": step, "reading": reading }))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+            }
+
+            // Score of each path from the letter values and cell bonuses
+            if !f.scores.is_empty() {
+                word["path_scores"] = json!(f.scores);
+            }
+
+            word
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = json!({
+        "board": {
+            "width": width,
+            "height": height,
+            "depth": depth,
+            "topology": details.board.topology().name(),
+            "faces": faces,
+        },
+        "game_type": details.game_type,
+        "seed": details.seed,
+        "probability": details.probability,
+        "difficulty": details.difficulty.map(|d| json!({
+            "level": d.level().to_string(),
+            "rating": d.rating,
+            "words": d.words,
+            "common_share": d.common_share,
+            "average_length": d.average_length,
+            "unused_cells": d.unused_cells,
+        })),
+        "dictionary": {
+            "file": details.dictionary_file,
+            "words": details.dictionary_words,
+            "alphabet": details.alphabet.to_string(),
+        },
+        "word_count": found.len(),
+        "max_score": details.rules.total_score(&found),
+        "words": words,
+    });
+
+    // Words and points through each cell laid out as the faces
+    if let Some(heat_map) = details.heat_map {
+        output["heat_map"] = grid_json(
+            details.board,
+            |cell| json!({ "words": heat_map[cell].words, "score": heat_map[cell].score }),
+        );
+    }
+
+    // Cell bonuses laid out as the faces
+    if details.board.has_bonuses() {
+        output["board"]["bonuses"] = grid_json(details.board, |cell| {
+            json!(details.board.cell_bonus(cell).to_string())
+        });
+    }
+
+    output
+}
+
+/// Converts a dice face to a JSON value
+fn face_json(face: &DiceFace) -> Value {
+    match face {
+        DiceFace::Letter(c) => json!({ "type": "letter", "value": c.to_string() }),
+        DiceFace::Ligature(s) => json!({ "type": "ligature", "value": s }),
+        DiceFace::Stop => json!({ "type": "stop" }),
+        DiceFace::Wildcard => json!({ "type": "wildcard" }),
+        DiceFace::Alternatives(readings) => json!({ "type": "alternatives", "value": readings }),
+    }
+}
+
+/// Converts a value for each cell to rows of values laid out as the board, with null for empty
+/// positions. Flat boards have a single layer of rows, 3D boards a list of layers
+fn grid_json<F>(board: &Board, cell_json: F) -> Value
+where
+    F: Fn(usize) -> Value,
+{
+    let (width, height) = board.dimension();
+
+    let layers = (0..board.depth())
+        .map(|z| {
+            (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| match board.cell_at_layer(x, y, z) {
+                            Some(cell) => cell_json(cell),
+                            None => Value::Null,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if board.depth() == 1 {
+        json!(layers[0])
+    } else {
+        json!(layers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dictionary::Dictionary;
+    use solver::{find_words, SolverArgs};
+
+    use super::*;
+
+    #[test]
+    fn document() {
+        // R U
+        // S ?
+        let board = Board::new(None, None, ["R", "U", "S", "?"].map(String::from).to_vec())
+            .unwrap()
+            .with_bonuses(board::CellBonus::from_list(". DL . .").unwrap())
+            .unwrap();
+        let dictionary =
+            Dictionary::new_from_string("rust\nrut\nsun", Default::default(), false).unwrap();
+        let rules = ScoreRules::for_board(&board);
+
+        let found = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        let output = results_json(
+            found,
+            JsonDetails {
+                board: &board,
+                game_type: None,
+                seed: Some(1),
+                dictionary_file: "words.txt",
+                dictionary_words: dictionary.word_count(),
+                alphabet: dictionary.alphabet(),
+                probability: None,
+                rules: &rules,
+                difficulty: None,
+                heat_map: None,
+            },
+        );
+
+        // Parse the document as printed
+        let doc: Value = serde_json::from_str(&format!("{output:#}")).unwrap();
+
+        assert_eq!(doc["board"]["width"], 2);
+        assert_eq!(doc["board"]["depth"], 1);
+        assert_eq!(doc["board"]["topology"], "square");
+        assert_eq!(doc["board"]["faces"][0][0]["value"], "R");
+        assert_eq!(doc["board"]["faces"][1][1]["type"], "wildcard");
+        assert_eq!(doc["board"]["bonuses"][0][1], "DL");
+        assert_eq!(doc["game_type"], Value::Null);
+        assert_eq!(doc["seed"], 1);
+        assert_eq!(doc["dictionary"]["words"], 3);
+        assert_eq!(doc["word_count"], 3);
+        assert_eq!(doc["max_score"], 3);
+
+        // Longest first then alphabetical
+        let words = doc["words"].as_array().unwrap();
+        assert_eq!(words[0]["word"], "RUST");
+        assert_eq!(words[0]["length"], 4);
+        assert_eq!(words[0]["score"], 1);
+        assert_eq!(
+            words[0]["paths"][0],
+            json!([[0, 0], [1, 0], [0, 1], [1, 1]])
+        );
+        assert_eq!(
+            words[0]["choices"][0],
+            json!([{ "step": 3, "reading": "T" }])
+        );
+        assert_eq!(words[1]["word"], "RUT");
+        assert_eq!(words[2]["word"], "SUN");
+        assert!(words[0].get("path_scores").is_none());
+
+        // Q/QU I
+        // T    E
+        let board = Board::new(
+            None,
+            None,
+            ["Q/QU", "I", "T", "E"].map(String::from).to_vec(),
+        )
+        .unwrap();
+        let dictionary =
+            Dictionary::new_from_string("quit\nquite", Default::default(), false).unwrap();
+        let rules = ScoreRules::for_board(&board);
+
+        let found = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        let output = results_json(
+            found,
+            JsonDetails {
+                board: &board,
+                game_type: None,
+                seed: None,
+                dictionary_file: "words.txt",
+                dictionary_words: dictionary.word_count(),
+                alphabet: dictionary.alphabet(),
+                probability: None,
+                rules: &rules,
+                difficulty: None,
+                heat_map: None,
+            },
+        );
+
+        let doc: Value = serde_json::from_str(&format!("{output:#}")).unwrap();
+
+        assert_eq!(doc["board"]["faces"][0][0]["type"], "alternatives");
+
+        let words = doc["words"].as_array().unwrap();
+        assert_eq!(words[0]["word"], "QUITE");
+        assert_eq!(
+            words[0]["choices"][0],
+            json!([{ "step": 0, "reading": "QU" }])
+        );
+        assert_eq!(words[1]["word"], "QUIT");
+    }
+}
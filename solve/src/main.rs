@@ -2,51 +2,169 @@
 
 //! Boggle game solver
 
+mod check;
+mod generate;
+mod heatmap;
+mod json;
+mod optimise;
+mod play;
+mod probability;
 mod results;
 
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use board::{Board, GameType};
-use clap::{Parser, ValueEnum};
-use dictionary::{Dictionary, WordSizeConstraint};
+use board::{Board, CellBonus, DiceFace, DiceSet, GameType, LetterValues, Topology};
+use clap::{Parser, Subcommand, ValueEnum};
+use dictionary::{Alphabet, Dictionary, WordSizeConstraint};
 use numformat::NumFormat;
-use solver::{find_words, SolverArgs};
-
-use crate::results::print_results;
+use solver::{
+    cell_usage,
+    find_words,
+    BoardConstraints,
+    CommonWords,
+    Difficulty,
+    GenerateArgs,
+    Objective,
+    OptimiseConfig,
+    ScoreRules,
+    SolverArgs,
+};
+
+use crate::check::check;
+use crate::generate::generate;
+use crate::heatmap::print_heat_map;
+use crate::json::{print_results_json, JsonDetails};
+use crate::optimise::{optimise, OptimiseArgs};
+use crate::play::play;
+use crate::probability::{print_distribution, print_probability};
+use crate::results::{print_difficulty, print_results, ShowPaths};
 
 /// Boggle letters game solver
 #[derive(Parser, Default)]
 #[clap(author, version, about)]
 struct Args {
-    /// Game type
-    #[clap(short = 'g', long = "game", default_value = "classic")]
-    game_type: ArgsGameType,
+    /// Command to run. If none given the board is solved
+    #[clap(subcommand)]
+    command: Option<ArgsCommand>,
+
+    /// Game type (classic, new, big-original, big-challenge, big-deluxe, big2012, super-big)
+    /// or a dice set file (TOML, or JSON with a .json extension)
+    #[clap(short = 'g', long = "game", default_value = "classic", global = true)]
+    game_type: String,
 
     /// Board width
-    #[clap(short = 'x', long = "width")]
+    #[clap(short = 'x', long = "width", global = true)]
     width: Option<u8>,
 
     /// Board height
-    #[clap(short = 'y', long = "height")]
+    #[clap(short = 'y', long = "height", global = true)]
     height: Option<u8>,
 
+    /// Number of board layers for a 3D board
+    #[clap(short = 'z', long = "depth", default_value_t = 1, global = true)]
+    depth: u8,
+
+    /// Board topology [default: square, or as given by the layout file]
+    #[clap(long = "topology", global = true)]
+    topology: Option<ArgsTopology>,
+
+    /// Board layout file for a shaped board (text, or JSON with a .json extension)
+    #[clap(short = 'l', long = "layout", global = true)]
+    layout: Option<String>,
+
+    /// Random board seed. If none given a random seed is chosen
+    #[clap(short = 's', long = "seed", global = true)]
+    seed: Option<u64>,
+
     /// Word list file
     #[clap(
         short = 'd',
         long = "dictionary",
         default_value_t = default_dict().into(),
+        global = true,
     )]
     dictionary_file: String,
 
+    /// Alphabet name (english, german, french, spanish, danish, norwegian, swedish, finnish)
+    /// or a list of the alphabet's letters
+    #[clap(
+        short = 'a',
+        long = "alphabet",
+        default_value = "english",
+        global = true
+    )]
+    alphabet: String,
+
+    /// Load the dictionary as a minimised directed acyclic word graph to save memory
+    #[clap(long = "minimise")]
+    minimise: bool,
+
+    /// Save the loaded dictionary as a compiled dictionary file which loads much faster
+    #[clap(long = "save-compiled")]
+    save_compiled: Option<String>,
+
     /// Minimum word length to find
-    #[clap(short = 'm', long = "min-len", default_value_t = 3)]
+    #[clap(short = 'm', long = "min-len", default_value_t = 3, global = true)]
     min_len: u8,
 
+    /// Show the dice path(s) for each word found
+    #[clap(short = 'p', long = "paths")]
+    paths: bool,
+
+    /// Show every distinct path for each word (implies --paths)
+    #[clap(long = "all-paths", global = true)]
+    all_paths: bool,
+
+    /// Output format
+    #[clap(short = 'f', long = "format", default_value = "text")]
+    format: ArgsFormat,
+
     /// Verbose output
-    #[clap(short = 'v', long = "verbose")]
+    #[clap(short = 'v', long = "verbose", global = true)]
     verbose: bool,
 
+    /// Check that the given dice faces could be rolled with the game type's dice
+    #[clap(long = "validate")]
+    validate: bool,
+
+    /// Show the probability of the board being rolled with the game type's dice
+    #[clap(long = "probability")]
+    probability: bool,
+
+    /// Rate the difficulty of the board
+    #[clap(long = "difficulty")]
+    difficulty: bool,
+
+    /// Word frequency list used to rate the share of common words on the board. Each line has
+    /// a word, optionally followed by its frequency, otherwise most frequent first
+    #[clap(long = "frequency-list", requires = "difficulty")]
+    frequency_list: Option<String>,
+
+    /// Number of the most frequent words in the frequency list counted as common
+    #[clap(long = "common-count", default_value_t = 10_000)]
+    common_count: usize,
+
+    /// Show how many words and points pass through each cell of the board
+    #[clap(long = "heat-map")]
+    heat_map: bool,
+
+    /// Read a dice face in more than one way, for example QU=Q/QU to read QU as Q or QU.
+    /// May be given more than once
+    #[clap(long = "reading", global = true)]
+    readings: Vec<String>,
+
+    /// Letter values for Ruzzle style scoring, either scrabble or a list of letter values,
+    /// for example A=1,B=3,C=3
+    #[clap(long = "letter-values", global = true)]
+    letter_values: Option<String>,
+
+    /// Score multipliers for each cell, row by row, for Ruzzle style scoring. Each cell is one
+    /// of . (none), DL, TL (double or triple letter) and DW, TW (double or triple word),
+    /// for example ". DL . TW . . . . DW . . . . . TL ."
+    #[clap(long = "bonuses", global = true)]
+    bonuses: Option<String>,
+
     /// Debug output
     #[clap(long = "debug")]
     debug: bool,
@@ -55,28 +173,129 @@ struct Args {
     dice_faces: Vec<String>,
 }
 
-#[derive(ValueEnum, Debug, Clone, Default)]
-enum ArgsGameType {
+#[derive(Subcommand, Debug, Clone)]
+enum ArgsCommand {
+    /// Play a timed game, entering words found on the board
+    Play {
+        /// Game time in seconds
+        #[clap(short = 't', long = "time", default_value_t = 180)]
+        time: u64,
+    },
+    /// Show the probability of each dice face for the game type
+    Distribution,
+    /// Search the board for a single word, showing its path or why it can't be made
+    Check {
+        /// Word to search for
+        word: String,
+        /// Dice faces to use. If none given a random board is generated for the game type.
+        dice_faces: Vec<String>,
+    },
+    /// Roll boards for the game type until enough meet constraints on the words found
+    Generate {
+        /// Number of boards to generate
+        #[clap(short = 'n', long = "count", default_value_t = 1)]
+        count: usize,
+        /// Maximum number of boards to roll
+        #[clap(long = "max-attempts", default_value_t = 10_000)]
+        max_attempts: usize,
+        /// Minimum number of words
+        #[clap(long = "min-words")]
+        min_words: Option<usize>,
+        /// Maximum number of words
+        #[clap(long = "max-words")]
+        max_words: Option<usize>,
+        /// Minimum total score
+        #[clap(long = "min-score")]
+        min_score: Option<u32>,
+        /// Maximum total score
+        #[clap(long = "max-score")]
+        max_score: Option<u32>,
+        /// Minimum length of the longest word
+        #[clap(long = "min-longest")]
+        min_longest: Option<usize>,
+        /// Word which must be on the board. May be given more than once
+        #[clap(long = "word")]
+        words: Vec<String>,
+    },
+    /// Search for the board for the game type with the highest score or most words
+    Optimise {
+        /// Number of iterations to run
+        #[clap(short = 'n', long = "iterations", default_value_t = 10_000)]
+        iterations: u64,
+        /// File to save the search state to. If the file exists the search is resumed from it
+        /// with the settings it was started with
+        #[clap(long = "state")]
+        state_file: Option<String>,
+        /// Maximise the number of words instead of the total score
+        #[clap(long = "words")]
+        words: bool,
+        /// Allow any letter of the alphabet on any cell instead of the game type's dice
+        #[clap(long = "any-letters")]
+        any_letters: bool,
+        /// Starting temperature. 0 only accepts changes which make the board no worse
+        #[clap(long = "temperature", default_value_t = 5.0)]
+        temperature: f64,
+        /// Factor the temperature is multiplied by after each iteration
+        #[clap(long = "cooling", default_value_t = 0.9995)]
+        cooling: f64,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq)]
+enum ArgsTopology {
+    /// Cells at the edges of the board have fewer neighbours
     #[default]
-    Classic,
-    New,
-    BigOriginal,
-    BigChallenge,
-    BigDeluxe,
-    Big2012,
-    SuperBig,
+    Square,
+    /// The edges of the board wrap around so every cell has 8 neighbours
+    Toroidal,
+    /// Hexagonal grid in offset rows, every cell has up to 6 neighbours
+    Hex,
+    /// Cells are only adjacent where linked by the layout file
+    Graph,
 }
 
-impl std::fmt::Display for ArgsGameType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+impl From<ArgsTopology> for Topology {
+    fn from(topology: ArgsTopology) -> Self {
+        match topology {
+            ArgsTopology::Square => Topology::Square,
+            ArgsTopology::Toroidal => Topology::Toroidal,
+            ArgsTopology::Hex => Topology::Hex,
+            ArgsTopology::Graph => Topology::Graph,
+        }
     }
 }
 
+#[derive(ValueEnum, Debug, Clone, Default, PartialEq)]
+enum ArgsFormat {
+    /// Column formatted text
+    #[default]
+    Text,
+    /// JSON document
+    Json,
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
     let args = Args::parse();
 
+    // Get built in game type or load dice set file
+    let game_type = match GameType::from_name(&args.game_type) {
+        Some(game_type) => game_type,
+        None if Path::new(&args.game_type).is_file() => {
+            GameType::Custom(DiceSet::from_file(&args.game_type)?)
+        }
+        None => Err(format!(
+            "Unknown game type {} (expected one of {} or a dice set file)",
+            args.game_type,
+            GameType::names().join(", ")
+        ))?,
+    };
+
+    if let Some(ArgsCommand::Distribution) = args.command {
+        print_distribution(&game_type);
+        return Ok(());
+    }
+
     // Check we have a dictionary
     if args.dictionary_file.is_empty() {
         eprintln!("No dictionary file given and none of the default dictionaries could be found.");
@@ -89,61 +308,404 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::process::exit(1);
     }
 
+    // Get the alphabet
+    let alphabet = match Alphabet::named(&args.alphabet) {
+        Some(alphabet) => alphabet,
+        None => Alphabet::new(&args.alphabet)?,
+    };
+
     // Load words
     let mut size = WordSizeConstraint::default();
 
     size.set_min(args.min_len as usize);
 
-    let dictionary = Dictionary::new_from_file(&args.dictionary_file, size, args.verbose)?;
-
-    let board = if args.dice_faces.is_empty() {
-        // Convert arg game type to game type
-        let game_type = match args.game_type {
-            ArgsGameType::Classic => GameType::Classic,
-            ArgsGameType::New => GameType::New,
-            ArgsGameType::BigOriginal => GameType::BigOriginal,
-            ArgsGameType::BigChallenge => GameType::BigChallenge,
-            ArgsGameType::BigDeluxe => GameType::BigDeluxe,
-            ArgsGameType::Big2012 => GameType::Big2012,
-            ArgsGameType::SuperBig => GameType::SuperBig,
+    let dictionary = if args.minimise {
+        Dictionary::new_minimised_from_file_with_alphabet(
+            &args.dictionary_file,
+            alphabet,
+            size,
+            args.verbose,
+        )?
+    } else {
+        Dictionary::new_from_file_with_alphabet(
+            &args.dictionary_file,
+            alphabet,
+            size,
+            args.verbose,
+        )?
+    };
+
+    if let Some(file) = &args.save_compiled {
+        dictionary.save_compiled(file)?;
+
+        if args.verbose {
+            eprintln!("Compiled dictionary saved to {file}");
+        }
+    }
+
+    // Changes to make to generated boards
+    let prepare = |mut board: Board| {
+        if let Some(topology) = args.topology {
+            board = board.with_topology(topology.into());
+        }
+
+        apply_board_options(board, &args)
+    };
+
+    if let Some(ArgsCommand::Generate {
+        count,
+        max_attempts,
+        min_words,
+        max_words,
+        min_score,
+        max_score,
+        min_longest,
+        words,
+    }) = &args.command
+    {
+        if args.layout.is_some() {
+            Err("Boards can't be generated from a layout file")?
+        }
+
+        let constraints = BoardConstraints {
+            min_words: *min_words,
+            max_words: *max_words,
+            min_score: *min_score,
+            max_score: *max_score,
+            min_longest: *min_longest,
+            words: words.clone(),
         };
 
+        generate(GenerateArgs {
+            game_type: &game_type,
+            dictionary: &dictionary,
+            constraints: &constraints,
+            prepare: Some(&prepare),
+            count: *count,
+            max_attempts: *max_attempts,
+            seed: args.seed.unwrap_or_else(rand::random),
+        })?;
+
+        return Ok(());
+    }
+
+    if let Some(ArgsCommand::Optimise {
+        iterations,
+        state_file,
+        words,
+        any_letters,
+        temperature,
+        cooling,
+    }) = &args.command
+    {
+        if args.layout.is_some() {
+            Err("Boards can't be optimised from a layout file")?
+        }
+
+        optimise(OptimiseArgs {
+            game_type: &game_type,
+            dictionary: &dictionary,
+            prepare: &prepare,
+            config: OptimiseConfig {
+                objective: if *words {
+                    Objective::Words
+                } else {
+                    Objective::Score
+                },
+                restrict_dice: !any_letters,
+                temperature: *temperature,
+                cooling: *cooling,
+                seed: args.seed.unwrap_or_else(rand::random),
+                options: board_options_string(&args),
+            },
+            iterations: *iterations,
+            state_file: state_file.as_deref(),
+        })?;
+
+        return Ok(());
+    }
+
+    let mut game_type_name = None;
+    let mut seed = None;
+
+    let dice_faces = match &args.command {
+        Some(ArgsCommand::Check { dice_faces, .. }) if args.dice_faces.is_empty() => dice_faces,
+        _ => &args.dice_faces,
+    };
+
+    if args.layout.is_some() && !dice_faces.is_empty() {
+        Err("Dice faces can't be given with a layout file")?
+    }
+
+    let mut board = if let Some(file) = &args.layout {
+        // Load shaped board
+        Board::from_layout_file(file, dictionary.alphabet())?
+    } else if dice_faces.is_empty() {
+        game_type_name = Some(game_type.name().to_string());
+
         // Generate board
-        Board::new_random(game_type)
+        let board_seed = args.seed.unwrap_or_else(rand::random);
+        seed = Some(board_seed);
+
+        Board::new_random_seeded(game_type.clone(), board_seed)
+            .with_alphabet(dictionary.alphabet())?
     } else {
-        Board::new(args.width, args.height, args.dice_faces)?
+        let board = Board::new_with_depth(
+            args.width,
+            args.height,
+            args.depth,
+            dice_faces.clone(),
+            dictionary.alphabet(),
+        )?;
+
+        if args.validate {
+            board.check_possible_with(&game_type).map_err(|e| {
+                format!(
+                    "Board can't be rolled with the {} dice: {e}",
+                    game_type.name()
+                )
+            })?;
+        }
+
+        board
     };
 
-    // Print board
-    println!("Board:");
-    board.print();
+    if let Some(topology) = args.topology {
+        board = board.with_topology(topology.into());
+    }
+
+    // Keep the faces as rolled for probabilities before applying alternative readings
+    let rolled_board = board.clone();
+
+    let board = apply_board_options(board, &args)?;
+
+    if let Some(ArgsCommand::Check { word, .. }) = &args.command {
+        if let Some(seed) = seed {
+            println!("Seed: {seed}");
+        }
+
+        println!("Board:");
+        board.print();
+
+        if board.has_bonuses() {
+            println!("Bonuses:");
+            board.print_bonuses();
+        }
+
+        let result = check(
+            SolverArgs {
+                board: &board,
+                dictionary: &dictionary,
+                all_paths: args.all_paths,
+                debug: args.debug,
+            },
+            &player_rules(&board, &args),
+            word,
+        );
+
+        if let Err(reason) = result {
+            println!("{} not found: {reason}", word.trim().to_uppercase());
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
 
     // Find words
     let start_time = Instant::now();
 
-    let words = find_words(SolverArgs {
+    // Rating difficulty and the heat map need every path to find the cells used
+    let cell_paths = args.difficulty || args.heat_map;
+
+    let mut words = find_words(SolverArgs {
         board: &board,
         dictionary: &dictionary,
+        all_paths: args.all_paths || cell_paths,
         debug: args.debug,
     });
 
     if args.verbose {
-        println!(
+        eprintln!(
             "Search took {} seconds",
             start_time.elapsed().as_secs_f64().num_format_sigdig(2)
         );
     }
 
+    let rules = ScoreRules::for_board(&board);
+
+    let difficulty = if args.difficulty {
+        let common = match &args.frequency_list {
+            Some(file) => Some(CommonWords::from_file(file, args.common_count)?),
+            None => None,
+        };
+
+        Some(Difficulty::rate(&board, &words, common.as_ref()))
+    } else {
+        None
+    };
+
+    let heat_map = args.heat_map.then(|| cell_usage(&board, &words, &rules));
+
+    // Only keep the paths requested
+    if cell_paths && !args.all_paths {
+        for word in &mut words {
+            word.paths.truncate(1);
+            word.choices.truncate(1);
+            word.scores.truncate(1);
+        }
+    }
+
+    if let Some(ArgsCommand::Play { time }) = args.command {
+        if let Some(seed) = seed {
+            println!("Seed: {seed}");
+        }
+
+        play(
+            &board,
+            &dictionary,
+            &player_rules(&board, &args),
+            words,
+            Duration::from_secs(time),
+        );
+
+        return Ok(());
+    }
+
+    // Print board
+    if args.format == ArgsFormat::Text {
+        if let Some(seed) = seed {
+            println!("Seed: {seed}");
+        }
+
+        println!("Board:");
+        board.print();
+
+        if board.has_bonuses() {
+            println!("Bonuses:");
+            board.print_bonuses();
+        }
+
+        if args.probability {
+            print_probability(&rolled_board, &game_type);
+        }
+
+        if let Some(difficulty) = &difficulty {
+            print_difficulty(difficulty);
+        }
+
+        if let Some(heat_map) = &heat_map {
+            print_heat_map(&board, heat_map);
+        }
+    }
+
     // Print results
-    print_results(words);
+    match args.format {
+        ArgsFormat::Text => {
+            let show_paths = if !args.paths && !args.all_paths {
+                ShowPaths::Hide
+            } else if board.depth() > 1 {
+                ShowPaths::Layered
+            } else {
+                ShowPaths::Flat
+            };
+
+            print_results(words, &rules, show_paths)
+        }
+        ArgsFormat::Json => print_results_json(
+            words,
+            JsonDetails {
+                board: &board,
+                game_type: game_type_name,
+                seed,
+                dictionary_file: &args.dictionary_file,
+                dictionary_words: dictionary.word_count(),
+                alphabet: dictionary.alphabet(),
+                probability: args
+                    .probability
+                    .then(|| rolled_board.probability_with(&game_type)),
+                rules: &rules,
+                difficulty: difficulty.as_ref(),
+                heat_map: heat_map.as_deref(),
+            },
+        ),
+    }
 
     Ok(())
 }
 
-const DICTS: [&str; 3] = [
+/// Describes the options which change generated boards, so a resumed optimiser run can check
+/// it uses the same options
+fn board_options_string(args: &Args) -> String {
+    let mut options = Vec::new();
+
+    if let Some(topology) = args.topology {
+        if let Some(value) = topology.to_possible_value() {
+            options.push(format!("--topology {}", value.get_name()));
+        }
+    }
+
+    for reading in &args.readings {
+        options.push(format!("--reading {reading}"));
+    }
+
+    if let Some(values) = &args.letter_values {
+        options.push(format!("--letter-values {values}"));
+    }
+
+    if let Some(bonuses) = &args.bonuses {
+        options.push(format!("--bonuses {bonuses}"));
+    }
+
+    options.join(" ")
+}
+
+/// Returns the scoring rules for words given by the player. Words shorter than the minimum
+/// length either don't score or weren't loaded into the dictionary, so they are too short
+fn player_rules(board: &Board, args: &Args) -> ScoreRules {
+    let rules = ScoreRules::for_board(board);
+
+    ScoreRules::new(rules.min_len().max(args.min_len as usize))
+}
+
+/// Applies the alternative readings, letter values and cell bonuses options to a board
+fn apply_board_options(mut board: Board, args: &Args) -> Result<Board, String> {
+    for reading in &args.readings {
+        let (from, to) = parse_reading(reading)?;
+        board = board.with_face_replaced(&from, &to)?;
+    }
+
+    if let Some(values) = &args.letter_values {
+        board = board.with_letter_values(match LetterValues::named(values) {
+            Some(values) => values,
+            None => LetterValues::from_string(values)?,
+        });
+    }
+
+    if let Some(bonuses) = &args.bonuses {
+        board = board.with_bonuses(CellBonus::from_list(bonuses)?)?;
+    }
+
+    Ok(board)
+}
+
+/// Parses a dice face reading option of the form FACE=READINGS
+fn parse_reading(reading: &str) -> Result<(DiceFace, DiceFace), String> {
+    let error =
+        || format!("Invalid reading {reading} (expected FACE=READINGS, for example QU=Q/QU)");
+
+    let (from, to) = reading.split_once('=').ok_or_else(error)?;
+
+    Ok((
+        DiceFace::from_string(from).map_err(|_| error())?,
+        DiceFace::from_string(to).map_err(|_| error())?,
+    ))
+}
+
+const DICTS: [&str; 4] = [
     "words.txt",
     "words.txt.gz",
     "/etc/dictionaries-common/words",
+    "words.dict",
 ];
 
 fn default_dict() -> &'static str {
@@ -160,3 +722,21 @@ fn dict_valid(dict: &str) -> Option<String> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_rules() {
+        let board = Board::new(None, None, vec!["A".to_string(); 25]).unwrap();
+
+        let min_len =
+            |args: &[&str]| super::player_rules(&board, &Args::parse_from(args)).min_len();
+
+        // Big boards score words of 4 letters, and longer words may be all that were loaded
+        assert_eq!(min_len(&["solve"]), 4);
+        assert_eq!(min_len(&["solve", "-m", "5"]), 5);
+        assert_eq!(min_len(&["solve", "play", "-m", "6"]), 6);
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs::{read_to_string, rename, write};
+use std::path::Path;
+
+use board::{Board, GameType};
+use dictionary::Dictionary;
+use numformat::NumFormat;
+use solver::{OptimiseConfig, Optimiser};
+
+/// Number of iterations between saves of the optimiser state
+const SAVE_INTERVAL: u64 = 100;
+
+/// Arguments for an optimiser run
+pub struct OptimiseArgs<'a> {
+    /// Game type to find the best board for
+    pub game_type: &'a GameType,
+    /// Dictionary to solve the boards with
+    pub dictionary: &'a Dictionary,
+    /// Changes to make to each board before it is solved
+    pub prepare: &'a dyn Fn(Board) -> Result<Board, String>,
+    /// Settings for a new run
+    pub config: OptimiseConfig,
+    /// Number of iterations to run
+    pub iterations: u64,
+    /// File to resume from and save the optimiser state to
+    pub state_file: Option<&'a str>,
+}
+
+/// Runs the optimiser, printing each new best board found. The state is saved regularly
+/// so the run can be interrupted and resumed
+pub fn optimise(args: OptimiseArgs) -> Result<(), String> {
+    let mut optimiser = match args.state_file {
+        Some(file) if Path::new(file).is_file() => {
+            let json = read_to_string(file)
+                .map_err(|e| format!("Unable to read optimiser state file {file}: {e}"))?;
+
+            let optimiser = Optimiser::from_json(
+                &json,
+                args.game_type,
+                args.dictionary,
+                Some(args.prepare),
+                &args.config.options,
+            )
+            .map_err(|e| format!("{file}: {e}"))?;
+
+            println!(
+                "Resuming at iteration {}, best {} {}",
+                optimiser.iteration().num_format(),
+                optimiser.objective().name(),
+                optimiser.best_value().num_format()
+            );
+
+            optimiser
+        }
+        _ => Optimiser::new(
+            args.game_type,
+            args.dictionary,
+            Some(args.prepare),
+            &args.config,
+        )?,
+    };
+
+    print_best(&optimiser);
+
+    for _ in 0..args.iterations {
+        if optimiser.step()? {
+            print_best(&optimiser);
+        }
+
+        if optimiser.iteration().is_multiple_of(SAVE_INTERVAL) {
+            save(&optimiser, args.state_file)?;
+        }
+    }
+
+    save(&optimiser, args.state_file)?;
+
+    println!(
+        "Finished at iteration {} (temperature {})",
+        optimiser.iteration().num_format(),
+        optimiser.temperature().num_format_sigdig(3)
+    );
+
+    Ok(())
+}
+
+/// Prints the best board found so far
+fn print_best(optimiser: &Optimiser) {
+    println!(
+        "Iteration {}: best {} {}",
+        optimiser.iteration().num_format(),
+        optimiser.objective().name(),
+        optimiser.best_value().num_format()
+    );
+
+    optimiser.best_board().print();
+}
+
+/// Saves the optimiser state if a state file was given. The state is written to a temporary
+/// file first and renamed over the state file, so an interrupted save leaves the last state
+fn save(optimiser: &Optimiser, state_file: Option<&str>) -> Result<(), String> {
+    if let Some(file) = state_file {
+        let temp_file = format!("{file}.tmp");
+
+        write(&temp_file, optimiser.to_json())
+            .and_then(|_| rename(&temp_file, file))
+            .map_err(|e| format!("Unable to write optimiser state file {file}: {e}"))?;
+    }
+
+    Ok(())
+}
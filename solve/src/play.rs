@@ -0,0 +1,325 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use board::Board;
+use dictionary::Dictionary;
+use numformat::NumFormat;
+use solver::{FoundWord, ScoreRules};
+
+use crate::results::{print_words, ShowPaths};
+
+/// Number of best missed words to show at the end of a game
+const BEST_MISSED: usize = 10;
+
+/// Result of checking a word entered by the player
+#[derive(Debug, PartialEq, Eq)]
+pub enum Guess {
+    /// Word accepted with the given score
+    Accepted(u32),
+    /// Word has already been entered
+    AlreadyFound,
+    /// Word is shorter than the minimum length
+    TooShort,
+    /// Word is in the dictionary but can't be made on the board
+    NotOnBoard,
+    /// Word is not in the dictionary
+    NotInDictionary,
+}
+
+/// State of a timed game
+pub struct Game<'a> {
+    dictionary: &'a Dictionary,
+    rules: &'a ScoreRules,
+    /// Words on the board keyed by word
+    solution: HashMap<String, FoundWord>,
+    /// Words found by the player
+    found: HashSet<String>,
+    /// Player's score so far
+    score: u32,
+}
+
+impl<'a> Game<'a> {
+    /// Creates a new game from the solver results for the board
+    pub fn new(
+        dictionary: &'a Dictionary,
+        rules: &'a ScoreRules,
+        solution: Vec<FoundWord>,
+    ) -> Self {
+        let solution = solution
+            .into_iter()
+            .filter(|f| f.word.chars().count() >= rules.min_len())
+            .map(|f| (f.word.clone(), f))
+            .collect();
+
+        Self {
+            dictionary,
+            rules,
+            solution,
+            found: HashSet::new(),
+            score: 0,
+        }
+    }
+
+    /// Checks a word entered by the player
+    pub fn guess(&mut self, word: &str) -> Guess {
+        let word = word.trim().to_uppercase();
+
+        if self.found.contains(&word) {
+            Guess::AlreadyFound
+        } else if word.chars().count() < self.rules.min_len() {
+            Guess::TooShort
+        } else if let Some(found) = self.solution.get(&word) {
+            let score = self.rules.found_score(found);
+
+            self.found.insert(word);
+            self.score += score;
+
+            Guess::Accepted(score)
+        } else if self.dictionary.contains_word(&word) {
+            Guess::NotOnBoard
+        } else {
+            Guess::NotInDictionary
+        }
+    }
+
+    /// Returns the number of words the player can find, including words scoring nothing
+    pub fn word_count(&self) -> usize {
+        self.solution.len()
+    }
+
+    /// Returns the player's score so far
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Ends the game returning the words found by the player and the words missed
+    pub fn finish(self) -> (Vec<FoundWord>, Vec<FoundWord>) {
+        let found = self.found;
+
+        self.solution
+            .into_values()
+            .partition(|f| found.contains(&f.word))
+    }
+}
+
+/// Plays a timed game on the board, reading words from stdin
+pub fn play(
+    board: &Board,
+    dictionary: &Dictionary,
+    rules: &ScoreRules,
+    solution: Vec<FoundWord>,
+    time: Duration,
+) {
+    let max_score = rules.total_score(&solution);
+
+    let mut game = Game::new(dictionary, rules, solution);
+    let max_words = game.word_count();
+
+    println!("Board:");
+    board.print();
+
+    if board.has_bonuses() {
+        println!("Bonuses:");
+        board.print_bonuses();
+    }
+
+    println!(
+        "Find words of {} or more letters in {} ({} words to find, maximum score {})",
+        rules.min_len(),
+        time_string(time),
+        max_words.num_format(),
+        max_score.num_format()
+    );
+    println!("Enter one word per line, an empty line shows the board again");
+
+    // Read lines from stdin on a separate thread so the countdown isn't blocked
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            match line {
+                Ok(line) => {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+
+    let end_time = Instant::now() + time;
+
+    loop {
+        let remaining = end_time.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            println!("Time's up!");
+            break;
+        }
+
+        print!("[{}] > ", time_string(remaining));
+        io::stdout().flush().ok();
+
+        let line = match rx.recv_timeout(remaining) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => {
+                println!();
+                println!("Time's up!");
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                println!();
+                break;
+            }
+        };
+
+        if line.trim().is_empty() {
+            board.print();
+            continue;
+        }
+
+        match game.guess(&line) {
+            Guess::Accepted(score) => println!("  +{score} (total {})", game.score()),
+            Guess::AlreadyFound => println!("  Already found"),
+            Guess::TooShort => println!("  Too short"),
+            Guess::NotOnBoard => println!("  Not on the board"),
+            Guess::NotInDictionary => println!("  Not in the dictionary"),
+        }
+    }
+
+    // Print game summary
+    let score = game.score();
+    let (found, mut missed) = game.finish();
+
+    println!();
+    println!(
+        "You found {} of {} words, score {} of {}",
+        found.len().num_format(),
+        max_words.num_format(),
+        score.num_format(),
+        max_score.num_format()
+    );
+
+    if !found.is_empty() {
+        println!();
+        println!("Your words:");
+        print_words(found, rules, ShowPaths::Hide);
+    }
+
+    if !missed.is_empty() {
+        println!();
+        println!("Missed words:");
+
+        // Sort missed words by highest score, then longest, then alphabetical
+        missed.sort_by(|a, b| {
+            rules
+                .found_score(b)
+                .cmp(&rules.found_score(a))
+                .then_with(|| b.word.chars().count().cmp(&a.word.chars().count()))
+                .then_with(|| a.word.cmp(&b.word))
+        });
+
+        println!(
+            "  Best: {}",
+            missed
+                .iter()
+                .take(BEST_MISSED)
+                .map(|f| format!("{} {}", f.word, rules.found_score(f)))
+                .collect::<Vec<_>>()
+                .join("  ")
+        );
+
+        print_words(missed, rules, ShowPaths::Hide);
+    }
+}
+
+/// Formats a duration as minutes and seconds, rounding up
+fn time_string(time: Duration) -> String {
+    let secs = time.as_secs() + u64::from(time.subsec_nanos() > 0);
+
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use board::LetterValues;
+    use solver::{find_words, SolverArgs};
+
+    use super::*;
+
+    #[test]
+    fn guess() {
+        // R U
+        // S T
+        let board =
+            Board::new(None, None, ["R", "U", "S", "T"].map(String::from).to_vec()).unwrap();
+        let dictionary =
+            Dictionary::new_from_string("rust\nrut\nrus\nut\ntrust", Default::default(), false)
+                .unwrap();
+        let rules = ScoreRules::for_board(&board);
+
+        let solution = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        let mut game = Game::new(&dictionary, &rules, solution);
+
+        assert_eq!(game.word_count(), 3);
+        assert_eq!(game.guess("rust"), Guess::Accepted(1));
+        assert_eq!(game.guess(" RUST "), Guess::AlreadyFound);
+        assert_eq!(game.guess("ut"), Guess::TooShort);
+        assert_eq!(game.guess("trust"), Guess::NotOnBoard);
+        assert_eq!(game.guess("tsur"), Guess::NotInDictionary);
+        assert_eq!(game.guess("Rut"), Guess::Accepted(1));
+        assert_eq!(game.score(), 2);
+
+        let (found, missed) = game.finish();
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(missed.len(), 1);
+        assert_eq!(missed[0].word, "RUS");
+    }
+
+    #[test]
+    fn word_count() {
+        // ? ?
+        // ? R
+        let board = Board::new(None, None, ["?", "?", "?", "R"].map(String::from).to_vec())
+            .unwrap()
+            .with_letter_values(LetterValues::scrabble());
+        let dictionary =
+            Dictionary::new_from_string("cat\nrat\nat", Default::default(), false).unwrap();
+        let rules = ScoreRules::for_board(&board);
+
+        let solution = find_words(SolverArgs {
+            board: &board,
+            dictionary: &dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        let cat = solution.iter().find(|f| f.word == "CAT").unwrap();
+        assert_eq!(rules.found_score(cat), 0);
+
+        // CAT only has wildcard paths and scores nothing but still counts, AT is too short
+        let mut game = Game::new(&dictionary, &rules, solution);
+
+        assert_eq!(game.word_count(), 2);
+        assert_eq!(game.guess("cat"), Guess::Accepted(0));
+        assert_eq!(game.guess("at"), Guess::TooShort);
+    }
+
+    #[test]
+    fn time() {
+        assert_eq!(time_string(Duration::from_secs(180)), "3:00");
+        assert_eq!(time_string(Duration::from_millis(59_001)), "1:00");
+        assert_eq!(time_string(Duration::from_millis(5_500)), "0:06");
+    }
+}
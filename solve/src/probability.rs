@@ -0,0 +1,61 @@
+use board::{Board, DiceFace, GameType};
+
+/// Prints the probability of a board being rolled and of each cell showing its face
+pub fn print_probability(board: &Board, game_type: &GameType) {
+    let prob = board.probability_with(game_type);
+
+    if prob == 0.0 {
+        println!("Board can't be rolled with the {} dice", game_type.name());
+        return;
+    }
+
+    println!(
+        "Board probability with the {} dice: {prob:.3e} (1 in {:.3e})",
+        game_type.name(),
+        1.0 / prob
+    );
+
+    let (width, height) = board.dimension();
+    let depth = board.depth();
+    let cells = board.cell_probabilities(game_type);
+
+    println!("Cell probabilities:");
+
+    for z in 0..depth {
+        if depth > 1 {
+            println!("Layer {}:", z + 1);
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                match board.cell_at_layer(x, y, z) {
+                    Some(cell) => print!(" {:>6.2}%", cells[cell] * 100.0),
+                    None => print!(" {:>7}", ""),
+                }
+            }
+            println!();
+        }
+    }
+}
+
+/// Prints the probability of each dice face for a game type
+pub fn print_distribution(game_type: &GameType) {
+    let faces = game_type.face_probabilities();
+
+    println!("Dice face probabilities for {}:", game_type.name());
+    println!("  Face  Per cell  Appears  Expected");
+
+    for f in faces {
+        let face = match f.face {
+            DiceFace::Stop => "Blank".to_string(),
+            face => face.to_string(),
+        };
+
+        println!(
+            "  {face:<5} {:>7.2}%  {:>6.2}%  {:>8.3}",
+            f.cell * 100.0,
+            f.appears * 100.0,
+            f.expected
+        );
+    }
+}
@@ -1,26 +1,50 @@
 use std::cmp::{max, Ordering};
 
 use numformat::NumFormat;
+use solver::{Difficulty, FoundWord, ScoreRules, WordChoices, WordPath};
 #[cfg(any(unix, windows))]
 use terminal_size::{terminal_size, Width};
 
-pub fn print_results(mut words: Vec<String>) {
+/// How to show the dice path(s) for each word
+#[derive(Clone, Copy, PartialEq)]
+pub enum ShowPaths {
+    /// Don't show paths
+    Hide,
+    /// Show (x, y) positions
+    Flat,
+    /// Show (x, y, z) positions for 3D boards
+    Layered,
+}
+
+pub fn print_results(found: Vec<FoundWord>, rules: &ScoreRules, show_paths: ShowPaths) {
+    println!(
+        "{} {} found, maximum score {}",
+        found.len().num_format(),
+        if found.len() == 1 { "word" } else { "words" },
+        rules.total_score(&found).num_format()
+    );
+
+    print_words(found, rules, show_paths);
+}
+
+/// Prints words grouped by length, longest first
+pub fn print_words(mut found: Vec<FoundWord>, rules: &ScoreRules, show_paths: ShowPaths) {
     // Sort words by longest first then alphabetical
-    words.sort_by(|a, b| {
-        let mut result = b.len().cmp(&a.len());
+    found.sort_by(|a, b| {
+        let mut result = b.word.chars().count().cmp(&a.word.chars().count());
 
         if result == Ordering::Equal {
-            result = a.cmp(b);
+            result = a.word.cmp(&b.word);
         }
 
         result
     });
 
-    println!(
-        "{} {} found",
-        words.len().num_format(),
-        if words.len() == 1 { "word" } else { "words" }
-    );
+    let words = found.iter().map(|f| f.word.as_str()).collect::<Vec<_>>();
+    let scores = found
+        .iter()
+        .map(|f| rules.found_score(f))
+        .collect::<Vec<_>>();
 
     // Group words by length
     let mut last_len = 0;
@@ -28,12 +52,14 @@ pub fn print_results(mut words: Vec<String>) {
     let mut groups = Vec::new();
 
     for (i, word) in words.iter().enumerate() {
-        if word.len() != last_len {
+        let len = word.chars().count();
+
+        if len != last_len {
             if last_len != 0 {
                 groups.push((last_len, last_start, i));
             }
 
-            last_len = word.len();
+            last_len = len;
             last_start = i;
         }
     }
@@ -48,18 +74,113 @@ pub fn print_results(mut words: Vec<String>) {
     for (wordlen, start, end) in groups {
         println!("== {} letter words ({}) ==", wordlen, end - start);
 
+        // Scores for words of this length. These only differ with letter values
+        let score_len = scores[start..end]
+            .iter()
+            .map(|s| s.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        if show_paths != ShowPaths::Hide {
+            for (f, score) in found[start..end].iter().zip(&scores[start..end]) {
+                for (i, (path, choices)) in f.paths.iter().zip(&f.choices).enumerate() {
+                    if i == 0 {
+                        println!(
+                            "  {} {:<score_len$}  {}",
+                            f.word,
+                            score,
+                            path_string(path, choices, show_paths)
+                        );
+                    } else {
+                        println!(
+                            "  {:width$}  {}",
+                            "",
+                            path_string(path, choices, show_paths),
+                            width = wordlen + score_len + 1
+                        );
+                    }
+                }
+            }
+
+            continue;
+        }
+
         let cols = if term_width > 0 {
-            max(1, (term_width as usize - 1) / (wordlen + 2))
+            max(1, (term_width as usize - 1) / (wordlen + score_len + 3))
         } else {
             1
         };
 
-        for line in words[start..end].chunks(cols) {
-            println!("  {}", line.join("  "))
+        let word_scores = words[start..end]
+            .iter()
+            .zip(&scores[start..end])
+            .collect::<Vec<_>>();
+
+        for line in word_scores.chunks(cols) {
+            println!(
+                "  {}",
+                line.iter()
+                    .map(|(w, score)| format!("{w} {score:<score_len$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+            )
         }
     }
 }
 
+/// Prints the difficulty rating of the board
+pub fn print_difficulty(difficulty: &Difficulty) {
+    let mut details = vec![
+        format!("{} words", difficulty.words.num_format()),
+        format!(
+            "average length {}",
+            difficulty.average_length.num_format_sigdig(2)
+        ),
+        format!(
+            "{} unused {}",
+            difficulty.unused_cells,
+            if difficulty.unused_cells == 1 {
+                "cell"
+            } else {
+                "cells"
+            }
+        ),
+    ];
+
+    if let Some(share) = difficulty.common_share {
+        details.push(format!("{}% common", (share * 100.0).round()));
+    }
+
+    println!(
+        "Difficulty: {} (rating {}: {})",
+        difficulty.level(),
+        difficulty.rating.round(),
+        details.join(", ")
+    );
+}
+
+/// Formats a word path as a list of (x, y) positions, or (x, y, z) positions for 3D boards.
+/// Positions of wildcard faces are followed by the letter chosen, and positions of alternatives
+/// faces by the reading used, for example (1,2)=E or (0,0)=QU
+pub fn path_string(path: &WordPath, choices: &WordChoices, show_paths: ShowPaths) -> String {
+    path.iter()
+        .enumerate()
+        .map(|(step, (x, y, z))| {
+            let pos = match show_paths {
+                ShowPaths::Layered => format!("({x},{y},{z})"),
+                _ => format!("({x},{y})"),
+            };
+
+            match choices.iter().find(|(s, _)| *s == step) {
+                Some((_, reading)) => format!("{pos}={reading}"),
+                None => pos,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[cfg(any(unix, windows))]
 fn terminal_width() -> u16 {
     if let Some((Width(w), _)) = terminal_size() {
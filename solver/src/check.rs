@@ -0,0 +1,256 @@
+use std::fmt::Display;
+
+use board::DiceFace;
+
+use crate::{
+    face_options,
+    found_word,
+    path_score,
+    FoundPath,
+    FoundWord,
+    ScoreRules,
+    SearchState,
+    SolverArgs,
+};
+
+/// Reason a word can't be made on the board
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WordNotFound {
+    /// The word is shorter than the minimum word length
+    TooShort(usize),
+    /// The word is not in the dictionary
+    NotInDictionary,
+    /// No path of adjacent cells on the board spells the word
+    NotOnBoard,
+}
+
+impl Display for WordNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordNotFound::TooShort(min_len) => {
+                write!(f, "too short, words need at least {min_len} letters")
+            }
+            WordNotFound::NotInDictionary => write!(f, "not in the dictionary"),
+            WordNotFound::NotOnBoard => {
+                write!(f, "no path of adjacent cells on the board spells the word")
+            }
+        }
+    }
+}
+
+/// Searches the board for a single word with the same rules as [`crate::find_words`],
+/// returning the path `find_words` would, or every path if `all_paths` is set. On boards with
+/// letter values the highest scoring path comes first. The board must use the dictionary's alphabet,
+/// see [`board::Board::with_alphabet`]
+pub fn find_word(
+    args: SolverArgs,
+    rules: &ScoreRules,
+    word: &str,
+) -> Result<FoundWord, WordNotFound> {
+    let word = word.trim();
+
+    if word.chars().count() < rules.min_len() {
+        Err(WordNotFound::TooShort(rules.min_len()))?
+    }
+
+    if !args.dictionary.contains_word(word) {
+        Err(WordNotFound::NotInDictionary)?
+    }
+
+    let alphabet = args.dictionary.alphabet();
+
+    let target = word
+        .chars()
+        .map(|c| alphabet.char_to_elem(c))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(WordNotFound::NotInDictionary)?;
+
+    // Every path is needed to pick the same one as find_words
+    let mut state = SearchState::new(args.board);
+    let mut paths = Vec::new();
+
+    for cell in 0..args.board.cell_count() {
+        if !matches!(args.board.cell_face(cell), DiceFace::Stop) {
+            find_word_rec(&args, &target, &mut state, cell, &mut paths);
+        }
+    }
+
+    if paths.is_empty() {
+        Err(WordNotFound::NotOnBoard)?
+    }
+
+    let mut found = found_word(&args, &target, paths);
+
+    if !args.all_paths {
+        found.paths.truncate(1);
+        found.choices.truncate(1);
+        found.scores.truncate(1);
+    }
+
+    Ok(found)
+}
+
+/// Continues the search for a word at a cell, adding the paths which spell the word
+fn find_word_rec(
+    args: &SolverArgs,
+    target: &[u8],
+    state: &mut SearchState,
+    cell: usize,
+    paths: &mut Vec<FoundPath>,
+) {
+    let in_chosen = state.chosen.len();
+    let step = state.path.len();
+
+    state.visited.insert(cell);
+    state.path.push(cell);
+    state.starts.push(in_chosen);
+
+    face_options(args, cell, |letters, choice| {
+        if !target[in_chosen..].starts_with(letters) {
+            return;
+        }
+
+        state.chosen.extend_from_slice(letters);
+
+        if choice {
+            state.choices.push((step, in_chosen, letters.len()));
+        }
+
+        if state.chosen.len() == target.len() {
+            // Found the word
+            paths.push(FoundPath {
+                cells: state.path.clone(),
+                choices: state.choices.clone(),
+                score: path_score(args.board, &state.path, &state.starts, &state.chosen),
+            });
+        } else {
+            for &next in args.board.cell_neighbours(cell) {
+                if !state.visited.contains(next)
+                    && !matches!(args.board.cell_face(next), DiceFace::Stop)
+                {
+                    find_word_rec(args, target, state, next, paths);
+                }
+            }
+        }
+
+        if choice {
+            state.choices.pop();
+        }
+
+        state.chosen.truncate(in_chosen);
+    });
+
+    state.visited.remove(cell);
+    state.path.pop();
+    state.starts.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use board::LetterValues;
+
+    use super::*;
+    use crate::test_helpers::{make_board, make_dictionary};
+
+    fn check(faces: &[&str], word: &str, all_paths: bool) -> Result<FoundWord, WordNotFound> {
+        find_word(
+            SolverArgs {
+                board: &make_board(faces),
+                dictionary: &make_dictionary("rust\nrut\ntrust\nquit\nquat\nqat\nat"),
+                all_paths,
+                debug: false,
+            },
+            &ScoreRules::new(3),
+            word,
+        )
+    }
+
+    #[test]
+    fn found() {
+        // R U
+        // S T
+        let found = check(&["R", "U", "S", "T"], "Rust", false).unwrap();
+
+        assert_eq!(found.word, "RUST");
+        assert_eq!(
+            found.paths,
+            vec![vec![(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)]]
+        );
+
+        // R U
+        // T T
+        let found = check(&["R", "U", "T", "T"], "rut", true).unwrap();
+        assert_eq!(found.paths.len(), 2);
+
+        // Ligatures, wildcards and alternatives follow the same rules as find_words
+        // QU A
+        // ?  T
+        let found = check(&["QU", "A", "?", "T"], "quit", false).unwrap();
+        assert_eq!(found.paths, vec![vec![(0, 0, 0), (0, 1, 0), (1, 1, 0)]]);
+        assert_eq!(found.choices, vec![vec![(1, "I".to_string())]]);
+
+        assert_eq!(
+            check(&["QU", "A", "S", "T"], "qat", false).err(),
+            Some(WordNotFound::NotOnBoard)
+        );
+
+        let found = check(&["Q/QU", "A", "S", "T"], "qat", false).unwrap();
+        assert_eq!(found.choices, vec![vec![(0, "Q".to_string())]]);
+    }
+
+    #[test]
+    fn not_found() {
+        let faces = ["R", "U", "", "T"];
+
+        assert_eq!(
+            check(&faces, "at", false).err(),
+            Some(WordNotFound::TooShort(3))
+        );
+        assert_eq!(
+            check(&faces, "rue", false).err(),
+            Some(WordNotFound::NotInDictionary)
+        );
+        assert_eq!(
+            check(&faces, "ru$", false).err(),
+            Some(WordNotFound::NotInDictionary)
+        );
+
+        // Blank faces stop words
+        assert!(check(&["R", "U", "T", ""], "rut", false).is_ok());
+        assert_eq!(
+            check(&faces, "trust", false).err(),
+            Some(WordNotFound::NotOnBoard)
+        );
+
+        // Cells can't be used twice
+        assert_eq!(
+            check(&["T", "R", "U", "S"], "trust", false).err(),
+            Some(WordNotFound::NotOnBoard)
+        );
+    }
+
+    #[test]
+    fn best_path() {
+        // R U
+        // T T
+        let board = make_board(&["R", "U", "T", "T"])
+            .with_letter_values(LetterValues::scrabble())
+            .with_bonuses(board::CellBonus::from_list(". . . DW").unwrap())
+            .unwrap();
+
+        let found = find_word(
+            SolverArgs {
+                board: &board,
+                dictionary: &make_dictionary("rut"),
+                all_paths: false,
+                debug: false,
+            },
+            &ScoreRules::new(3),
+            "rut",
+        )
+        .unwrap();
+
+        assert_eq!(found.paths, vec![vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]]);
+        assert_eq!(found.scores, vec![6]);
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs::read_to_string;
+
+use board::Board;
+
+use crate::heatmap::mark_path_cells;
+use crate::{FoundWord, ScoreRules};
+
+/// Words per board cell giving a word count hardness of one half
+const WORDS_PER_CELL: f64 = 10.0;
+/// Letters over the minimum word length in the average word giving a length hardness of one
+const LENGTH_EXCESS: f64 = 1.7;
+/// Share of the board's cells unused giving an unused cells hardness of one
+const UNUSED_SHARE: f64 = 0.25;
+
+/// Weights of the word count, common word share, average length and unused cells hardness
+const WEIGHTS: [f64; 4] = [0.4, 0.3, 0.15, 0.15];
+
+/// Ratings below this are easy
+const MEDIUM_RATING: f64 = 35.0;
+/// Ratings from this are hard
+const HARD_RATING: f64 = 45.0;
+
+/// Set of common words taken from a word frequency list
+pub struct CommonWords {
+    words: HashSet<String>,
+}
+
+impl CommonWords {
+    /// Creates a set of common words
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().to_uppercase())
+                .collect(),
+        }
+    }
+
+    /// Loads the most frequent words from a word frequency list file
+    pub fn from_file(file: &str, count: usize) -> Result<Self, String> {
+        let contents = read_to_string(file)
+            .map_err(|e| format!("Unable to read word frequency file {file}: {e}"))?;
+
+        Self::from_frequency_list(&contents, count).map_err(|e| format!("{file}: {e}"))
+    }
+
+    /// Takes the most frequent words from a word frequency list. Each line has a word,
+    /// optionally followed by its frequency. Without frequencies the words are taken to be
+    /// in order of frequency, most frequent first
+    pub fn from_frequency_list(string: &str, count: usize) -> Result<Self, String> {
+        let mut words = string
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_no, line)| {
+                let mut fields = line.split_whitespace();
+
+                let word = fields.next().unwrap_or_default();
+
+                let frequency = match fields.next() {
+                    Some(f) => f
+                        .parse::<f64>()
+                        .map_err(|_| format!("Line {}: Invalid frequency {f}", line_no + 1))?,
+                    None => f64::INFINITY,
+                };
+
+                Ok((word, frequency))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // Most frequent first, keeping the file order for equal frequencies
+        words.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Ok(Self::new(words.into_iter().take(count).map(|(w, _)| w)))
+    }
+
+    /// Returns true if the upper case word is common
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+/// Difficulty label for a board
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DifficultyLevel {
+    /// Rating below 35
+    Easy,
+    /// Rating from 35 to below 45
+    Medium,
+    /// Rating from 45
+    Hard,
+}
+
+impl Display for DifficultyLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            DifficultyLevel::Easy => "easy",
+            DifficultyLevel::Medium => "medium",
+            DifficultyLevel::Hard => "hard",
+        })
+    }
+}
+
+/// Difficulty of a board
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    /// Number of words of at least the minimum scoring length
+    pub words: usize,
+    /// Share of the words which are common, if common words were given
+    pub common_share: Option<f64>,
+    /// Average word length in letters
+    pub average_length: f64,
+    /// Number of cells not on the path of any word
+    pub unused_cells: usize,
+    /// Rating from 0 (easiest) to 100 (hardest)
+    pub rating: f64,
+}
+
+impl Difficulty {
+    /// Rates the difficulty of a board from the words found on it. Only words of at least the
+    /// minimum scoring length count. Fewer words, fewer common words, longer words and more
+    /// unused cells make the board harder. Each is scaled from 0 to 1 and combined in a
+    /// weighted average, leaving out the common words if none are given. The scales are
+    /// calibrated with the included word list so that random classic boards split roughly
+    /// into thirds of easy, medium and hard boards. Random boards of the larger game types
+    /// rate easier, with most of them easy.
+    ///
+    /// Cells count as used if they're on any of the word's paths found, so solve with
+    /// `all_paths` to count every cell which can be used
+    pub fn rate(board: &Board, found: &[FoundWord], common: Option<&CommonWords>) -> Self {
+        let min_len = ScoreRules::for_board(board).min_len();
+
+        let found = found
+            .iter()
+            .filter(|f| f.word.chars().count() >= min_len)
+            .collect::<Vec<_>>();
+
+        let words = found.len();
+        let cells = board.cell_count();
+
+        let common_share = common.map(|common| {
+            if words == 0 {
+                0.0
+            } else {
+                found.iter().filter(|f| common.contains(&f.word)).count() as f64 / words as f64
+            }
+        });
+
+        let average_length = if words == 0 {
+            0.0
+        } else {
+            found.iter().map(|f| f.word.chars().count()).sum::<usize>() as f64 / words as f64
+        };
+
+        // Find cells used by any path
+        let mut used = vec![false; cells];
+
+        for f in &found {
+            mark_path_cells(board, f, &mut used);
+        }
+
+        let unused_cells = used.iter().filter(|u| !**u).count();
+
+        // Hardness of each measure from 0 to 1
+        let hardness = [
+            Some(1.0 / (1.0 + (words as f64 / (cells as f64 * WORDS_PER_CELL)).powi(2))),
+            common_share.map(|share| 1.0 - share),
+            Some(if words == 0 {
+                1.0
+            } else {
+                ((average_length - min_len as f64) / LENGTH_EXCESS).clamp(0.0, 1.0)
+            }),
+            Some((unused_cells as f64 / (cells as f64 * UNUSED_SHARE)).min(1.0)),
+        ];
+
+        let (total, weights) = hardness
+            .iter()
+            .zip(WEIGHTS)
+            .filter_map(|(h, w)| h.map(|h| (h * w, w)))
+            .fold((0.0, 0.0), |(total, weights), (h, w)| {
+                (total + h, weights + w)
+            });
+
+        Self {
+            words,
+            common_share,
+            average_length,
+            unused_cells,
+            rating: 100.0 * total / weights,
+        }
+    }
+
+    /// Returns the difficulty label for the rating
+    pub fn level(&self) -> DifficultyLevel {
+        if self.rating < MEDIUM_RATING {
+            DifficultyLevel::Easy
+        } else if self.rating < HARD_RATING {
+            DifficultyLevel::Medium
+        } else {
+            DifficultyLevel::Hard
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{make_board, make_dictionary, solve_board};
+
+    #[test]
+    fn common() {
+        let common = CommonWords::from_frequency_list("the\nof\nand\nto", 2).unwrap();
+        assert!(common.contains("THE"));
+        assert!(common.contains("OF"));
+        assert!(!common.contains("AND"));
+
+        let common = CommonWords::from_frequency_list("cat 10\ndog 30\n\nrat 20", 2).unwrap();
+        assert!(common.contains("DOG"));
+        assert!(common.contains("RAT"));
+        assert!(!common.contains("CAT"));
+
+        let err = CommonWords::from_frequency_list("cat 10\ndog x", 2)
+            .err()
+            .unwrap();
+        assert_eq!(err, "Line 2: Invalid frequency x");
+    }
+
+    #[test]
+    fn rate() {
+        // C A
+        // T S
+        let board = make_board(&["C", "A", "T", "S"]);
+
+        let rate = |words, common: Option<&CommonWords>| {
+            let found = solve_board(&board, &make_dictionary(words), true);
+
+            Difficulty::rate(&board, &found, common)
+        };
+
+        // Short words don't count
+        let difficulty = rate("cat\nact\nas", None);
+        assert_eq!(difficulty.words, 2);
+        assert_eq!(difficulty.common_share, None);
+        assert_eq!(difficulty.average_length, 3.0);
+        assert_eq!(difficulty.unused_cells, 1);
+
+        let easier = rate("cat\nact\ncast\nscat\ncats\ntacs", None);
+        assert_eq!(easier.unused_cells, 0);
+        assert!(easier.rating < difficulty.rating);
+
+        // Common words make it easier
+        let common = CommonWords::new(["cat", "act"]);
+        let with_common = rate("cat\nact\nas", Some(&common));
+        assert_eq!(with_common.common_share, Some(1.0));
+        assert!(with_common.rating < difficulty.rating);
+
+        // No words is as hard as possible
+        let hardest = rate("dog", Some(&common));
+        assert_eq!(hardest.unused_cells, 4);
+        assert_eq!(hardest.rating, 100.0);
+        assert_eq!(hardest.level(), DifficultyLevel::Hard);
+    }
+
+    #[test]
+    fn level() {
+        let difficulty = |rating| Difficulty {
+            words: 0,
+            common_share: None,
+            average_length: 0.0,
+            unused_cells: 0,
+            rating,
+        };
+
+        assert_eq!(difficulty(34.9).level(), DifficultyLevel::Easy);
+        assert_eq!(difficulty(35.0).level(), DifficultyLevel::Medium);
+        assert_eq!(difficulty(45.0).level(), DifficultyLevel::Hard);
+        assert_eq!(DifficultyLevel::Medium.to_string(), "medium");
+    }
+}
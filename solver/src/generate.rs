@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use board::{Board, GameType};
+use dictionary::Dictionary;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{find_words, FoundWord, ScoreRules, SolverArgs};
+
+/// Constraints a generated board must meet. Unset constraints always pass
+#[derive(Clone, Debug, Default)]
+pub struct BoardConstraints {
+    /// Minimum number of words on the board
+    pub min_words: Option<usize>,
+    /// Maximum number of words on the board
+    pub max_words: Option<usize>,
+    /// Minimum total score of the words on the board
+    pub min_score: Option<u32>,
+    /// Maximum total score of the words on the board
+    pub max_score: Option<u32>,
+    /// Minimum length in letters of the longest word on the board
+    pub min_longest: Option<usize>,
+    /// Words which must be on the board
+    pub words: Vec<String>,
+}
+
+/// Reason a generated board was rejected
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rejection {
+    /// Fewer words than the minimum
+    TooFewWords,
+    /// More words than the maximum
+    TooManyWords,
+    /// Total score below the minimum
+    ScoreTooLow,
+    /// Total score above the maximum
+    ScoreTooHigh,
+    /// Longest word shorter than the minimum
+    LongestTooShort,
+    /// A required word is not on the board
+    MissingWord(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::TooFewWords => write!(f, "too few words"),
+            Rejection::TooManyWords => write!(f, "too many words"),
+            Rejection::ScoreTooLow => write!(f, "score too low"),
+            Rejection::ScoreTooHigh => write!(f, "score too high"),
+            Rejection::LongestTooShort => write!(f, "longest word too short"),
+            Rejection::MissingWord(word) => write!(f, "{word} not on the board"),
+        }
+    }
+}
+
+impl BoardConstraints {
+    /// Checks the words found on a board against the constraints, returning every constraint
+    /// which fails
+    pub fn check(&self, found: &[FoundWord], rules: &ScoreRules) -> Vec<Rejection> {
+        let mut result = Vec::new();
+
+        let words = found.len();
+
+        if self.min_words.is_some_and(|min| words < min) {
+            result.push(Rejection::TooFewWords);
+        }
+
+        if self.max_words.is_some_and(|max| words > max) {
+            result.push(Rejection::TooManyWords);
+        }
+
+        if self.min_score.is_some() || self.max_score.is_some() {
+            let score = rules.total_score(found);
+
+            if self.min_score.is_some_and(|min| score < min) {
+                result.push(Rejection::ScoreTooLow);
+            }
+
+            if self.max_score.is_some_and(|max| score > max) {
+                result.push(Rejection::ScoreTooHigh);
+            }
+        }
+
+        if let Some(min) = self.min_longest {
+            let longest = found
+                .iter()
+                .map(|f| f.word.chars().count())
+                .max()
+                .unwrap_or(0);
+
+            if longest < min {
+                result.push(Rejection::LongestTooShort);
+            }
+        }
+
+        for word in &self.words {
+            let word = word.to_uppercase();
+
+            if !found.iter().any(|f| f.word == word) {
+                result.push(Rejection::MissingWord(word));
+            }
+        }
+
+        result
+    }
+}
+
+/// Arguments for the board generator
+pub struct GenerateArgs<'a> {
+    /// Game type to roll boards for
+    pub game_type: &'a GameType,
+    /// Dictionary to solve the boards with
+    pub dictionary: &'a Dictionary,
+    /// Constraints the boards must meet
+    pub constraints: &'a BoardConstraints,
+    /// Changes to make to each rolled board before it is solved, for example setting the
+    /// topology or letter values
+    pub prepare: Option<&'a dyn Fn(Board) -> Result<Board, String>>,
+    /// Number of boards to generate
+    pub count: usize,
+    /// Maximum number of boards to roll
+    pub max_attempts: usize,
+    /// Seed for the sequence of board seeds
+    pub seed: u64,
+}
+
+/// Board accepted by the generator
+pub struct GeneratedBoard {
+    /// Seed which rolls the board with [`Board::new_random_seeded`]
+    pub seed: u64,
+    /// The board
+    pub board: Board,
+    /// Words found on the board
+    pub words: Vec<FoundWord>,
+}
+
+/// Board generator acceptance statistics
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GenerateStats {
+    /// Number of boards rolled
+    pub attempts: usize,
+    /// Number of boards meeting the constraints
+    pub accepted: usize,
+    /// Number of boards failing each constraint. A board can fail more than one
+    pub rejections: BTreeMap<Rejection, usize>,
+}
+
+impl GenerateStats {
+    /// Returns the proportion of boards rolled which were accepted
+    pub fn acceptance_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.accepted as f64 / self.attempts as f64
+        }
+    }
+}
+
+/// Rolls boards for a game type until enough meet the constraints or the maximum number of
+/// attempts is reached. Each board is rolled from its own seed, generated from the seed
+/// given, so accepted boards can be rolled again
+pub fn generate_boards(args: GenerateArgs) -> Result<(Vec<GeneratedBoard>, GenerateStats), String> {
+    let mut rng = ChaCha8Rng::seed_from_u64(args.seed);
+    let mut boards = Vec::new();
+    let mut stats = GenerateStats::default();
+
+    while boards.len() < args.count && stats.attempts < args.max_attempts {
+        let seed = rng.gen();
+
+        let mut board = Board::new_random_seeded(args.game_type.clone(), seed)
+            .with_alphabet(args.dictionary.alphabet())?;
+
+        if let Some(prepare) = args.prepare {
+            board = prepare(board)?;
+        }
+
+        let words = find_words(SolverArgs {
+            board: &board,
+            dictionary: args.dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        stats.attempts += 1;
+
+        let rejections = args
+            .constraints
+            .check(&words, &ScoreRules::for_board(&board));
+
+        if rejections.is_empty() {
+            stats.accepted += 1;
+            boards.push(GeneratedBoard { seed, board, words });
+        } else {
+            for rejection in rejections {
+                *stats.rejections.entry(rejection).or_default() += 1;
+            }
+        }
+    }
+
+    Ok((boards, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{make_board, make_dictionary, solve_board};
+
+    fn dictionary() -> Dictionary {
+        make_dictionary("cat\nact\ntac\ncast\nscat")
+    }
+
+    #[test]
+    fn check() {
+        // C A
+        // T S
+        let found = solve_board(&make_board(&["C", "A", "T", "S"]), &dictionary(), false);
+        let rules = ScoreRules::new(3);
+
+        assert!(BoardConstraints::default().check(&found, &rules).is_empty());
+
+        let constraints = BoardConstraints {
+            min_words: Some(5),
+            max_words: Some(5),
+            min_score: Some(1),
+            max_score: Some(5),
+            min_longest: Some(4),
+            words: vec!["scat".into()],
+        };
+        assert!(constraints.check(&found, &rules).is_empty());
+
+        let constraints = BoardConstraints {
+            min_words: Some(6),
+            max_words: Some(4),
+            min_score: Some(6),
+            max_score: Some(4),
+            min_longest: Some(5),
+            words: vec!["cats".into()],
+        };
+        assert_eq!(
+            constraints.check(&found, &rules),
+            vec![
+                Rejection::TooFewWords,
+                Rejection::TooManyWords,
+                Rejection::ScoreTooLow,
+                Rejection::ScoreTooHigh,
+                Rejection::LongestTooShort,
+                Rejection::MissingWord("CATS".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let dictionary = dictionary();
+        let game_type = GameType::from_name("classic").unwrap();
+        let constraints = BoardConstraints {
+            min_words: Some(1),
+            ..Default::default()
+        };
+
+        let generate = |count, max_attempts| {
+            generate_boards(GenerateArgs {
+                game_type: &game_type,
+                dictionary: &dictionary,
+                constraints: &constraints,
+                prepare: None,
+                count,
+                max_attempts,
+                seed: 1,
+            })
+            .unwrap()
+        };
+
+        let words = |boards: &[GeneratedBoard]| {
+            boards
+                .iter()
+                .map(|b| {
+                    let mut words = b.words.iter().map(|w| w.word.clone()).collect::<Vec<_>>();
+                    words.sort();
+                    (b.seed, words)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (boards, stats) = generate(2, 10_000);
+
+        assert_eq!(boards.len(), 2);
+        assert_eq!(stats.accepted, boards.len());
+        assert_eq!(
+            stats.attempts,
+            stats.accepted + stats.rejections.values().sum::<usize>()
+        );
+
+        // Accepted boards meet the constraints and can be rolled again from their seed
+        for generated in &boards {
+            let rules = ScoreRules::for_board(&generated.board);
+            assert!(constraints.check(&generated.words, &rules).is_empty());
+
+            let board = Board::new_random_seeded(game_type.clone(), generated.seed);
+            assert_eq!(board.face(0, 0), generated.board.face(0, 0));
+        }
+
+        // The same seed generates the same boards
+        let (again, _) = generate(2, 10_000);
+        assert_eq!(words(&again), words(&boards));
+
+        // Stops after the maximum number of attempts
+        let (boards, stats) = generate(2, 1);
+
+        assert_eq!(stats.attempts, 1);
+        assert_eq!(stats.accepted, boards.len());
+    }
+}
@@ -0,0 +1,78 @@
+use board::Board;
+
+use crate::{FoundWord, ScoreRules};
+
+/// Usage of a board cell by the words found
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellUsage {
+    /// Number of words with a path through the cell
+    pub words: usize,
+    /// Total score of the words with a path through the cell
+    pub score: u32,
+}
+
+/// Returns how many of the words found, and how many points, pass through each cell of the
+/// board, indexed by cell number. A word counts once for each cell on any of its paths, so
+/// solve with `all_paths` to count every cell a word can use. Cells no word passes through
+/// are dead
+pub fn cell_usage(board: &Board, found: &[FoundWord], rules: &ScoreRules) -> Vec<CellUsage> {
+    let mut usage = vec![CellUsage::default(); board.cell_count()];
+    let mut used = vec![false; board.cell_count()];
+
+    for f in found {
+        let score = rules.found_score(f);
+
+        used.fill(false);
+        mark_path_cells(board, f, &mut used);
+
+        for (usage, _) in usage.iter_mut().zip(&used).filter(|(_, used)| **used) {
+            usage.words += 1;
+            usage.score += score;
+        }
+    }
+
+    usage
+}
+
+/// Marks the cells on any of a word's paths as used, indexed by cell number
+pub(crate) fn mark_path_cells(board: &Board, found: &FoundWord, used: &mut [bool]) {
+    for path in &found.paths {
+        for &(x, y, z) in path {
+            if let Some(cell) = board.cell_at_layer(x, y, z) {
+                used[cell] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{make_dictionary, solve_board};
+
+    #[test]
+    fn usage() {
+        // C A .
+        // T S X
+        let board = Board::new(
+            Some(3),
+            None,
+            ["C", "A", "", "T", "S", "X"].map(String::from).to_vec(),
+        )
+        .unwrap();
+        let found = solve_board(&board, &make_dictionary("cat\nact\ncast\nsat"), true);
+
+        let usage = cell_usage(&board, &found, &ScoreRules::new(3));
+
+        let words = usage.iter().map(|u| u.words).collect::<Vec<_>>();
+        assert_eq!(words, vec![3, 4, 0, 4, 2, 0]);
+
+        // Each word scores 1
+        assert_eq!(usage[1].score, 4);
+        assert_eq!(usage[4].score, 2);
+
+        // Words too short to score count with no points
+        let usage = cell_usage(&board, &found, &ScoreRules::new(4));
+        assert_eq!(usage[1], CellUsage { words: 4, score: 1 });
+    }
+}
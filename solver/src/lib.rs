@@ -2,118 +2,794 @@
 
 //! Boggle game solver
 
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 
 use board::{Board, DiceFace};
 use dictionary::Dictionary;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod check;
+mod difficulty;
+mod generate;
+mod heatmap;
+mod optimise;
+mod score;
+#[cfg(test)]
+mod test_helpers;
+
+pub use check::{find_word, WordNotFound};
+pub use difficulty::{CommonWords, Difficulty, DifficultyLevel};
+pub use generate::{
+    generate_boards,
+    BoardConstraints,
+    GenerateArgs,
+    GenerateStats,
+    GeneratedBoard,
+    Rejection,
+};
+pub use heatmap::{cell_usage, CellUsage};
+pub use optimise::{Objective, OptimiseConfig, Optimiser};
+use score::path_score;
+pub use score::ScoreRules;
 
 /// Arguments for the Boggle solver
 pub struct SolverArgs<'a> {
-    /// String of letters to use (must be upper case A-Z)
+    /// Board to solve
     pub board: &'a Board,
-    /// Dictionary to use
+    /// Dictionary to use (must use the same alphabet as the board)
     pub dictionary: &'a Dictionary,
+    /// Collect every distinct path for each word instead of just the first one found
+    pub all_paths: bool,
     /// Debug output
     pub debug: bool,
 }
 
-/// Find words in the dictionary on the board
-pub fn find_words(args: SolverArgs) -> Vec<String> {
-    let mut result = HashSet::new();
+/// Path through the board as a list of (x, y, z) dice positions. z is the layer of a 3D board
+/// and is always 0 on flat boards
+pub type WordPath = Vec<(usize, usize, usize)>;
 
-    // Vector of chosen letter elements
-    let mut chosen = Vec::new();
+/// Letters chosen for the wildcard and alternatives faces on a path as a list of
+/// (path step, letters)
+pub type WordChoices = Vec<(usize, String)>;
 
-    // Set of visited dice
-    let mut visited = HashSet::new();
+/// Word found on the board
+pub struct FoundWord {
+    /// The word (upper case)
+    pub word: String,
+    /// Paths through the board spelling the word. Always contains at least one path
+    pub paths: Vec<WordPath>,
+    /// Letters the wildcard faces stood for and readings used for alternatives faces on each
+    /// path, in the same order as the paths. Empty for paths without either face
+    pub choices: Vec<WordChoices>,
+    /// Score of each path from the board's letter values and cell bonuses, in the same order
+    /// as the paths. The highest scoring path comes first. Empty if the board has no letter
+    /// values
+    pub scores: Vec<u32>,
+}
 
-    // Start search recursion
-    let (bx, by) = args.board.dimension();
+/// Path found during the search
+struct FoundPath {
+    /// Cells visited
+    cells: Vec<usize>,
+    /// Letters chosen for wildcard and alternatives faces as (path step, start, length) in
+    /// the word's letter elements
+    choices: Vec<(usize, usize, usize)>,
+    /// Score of the path if the board has letter values
+    score: Option<u32>,
+}
 
-    for y in 0..by {
-        for x in 0..bx {
-            if args.debug {
-                println!("Starting at {x}x{y}");
-            }
+impl FoundPath {
+    /// Returns the sort key for a word's paths: highest score first, then the smallest cell
+    /// sequence, so the paths and the one kept without `all_paths` don't depend on the order
+    /// the cells were searched in
+    fn order(&self) -> PathOrder<'_> {
+        path_order(self.score, &self.cells, &self.choices)
+    }
+}
+
+/// Sort key for a word's paths, see [`FoundPath::order`]
+type PathOrder<'a> = (
+    Reverse<Option<u32>>,
+    &'a [usize],
+    &'a [(usize, usize, usize)],
+);
+
+/// Returns the sort key for a path, see [`FoundPath::order`]
+fn path_order<'a>(
+    score: Option<u32>,
+    cells: &'a [usize],
+    choices: &'a [(usize, usize, usize)],
+) -> PathOrder<'a> {
+    (Reverse(score), cells, choices)
+}
 
-            find_words_rec(&args, &mut chosen, &mut visited, x, y, 0, &mut result);
+/// Working state for the word search recursion
+struct SearchState {
+    /// Vector of chosen letter elements
+    chosen: Vec<u8>,
+    /// Cells chosen so far
+    path: Vec<usize>,
+    /// Start of each chosen cell's letter elements in the chosen vector
+    starts: Vec<usize>,
+    /// Letters chosen for wildcard and alternatives faces so far as (path step, start, length)
+    choices: Vec<(usize, usize, usize)>,
+    /// Set of visited cells
+    visited: CellSet,
+    /// Words found so far (as letter elements) with their paths
+    result: HashMap<Vec<u8>, Vec<FoundPath>>,
+}
+
+impl SearchState {
+    fn new(board: &Board) -> Self {
+        let cells = board.cell_count();
+
+        Self {
+            chosen: Vec::with_capacity(cells * 2),
+            path: Vec::with_capacity(cells),
+            starts: Vec::with_capacity(cells),
+            choices: Vec::new(),
+            visited: CellSet::new(cells),
+            result: HashMap::new(),
         }
     }
+}
+
+/// Find words in the dictionary on the board.
+/// With the `parallel` feature enabled each starting cell is searched on the rayon thread pool.
+/// The board must use the dictionary's alphabet, see [`Board::with_alphabet`]
+pub fn find_words(args: SolverArgs) -> Vec<FoundWord> {
+    #[cfg(not(feature = "parallel"))]
+    let result = {
+        let mut state = SearchState::new(args.board);
+
+        for cell in 0..args.board.cell_count() {
+            find_words_from(&args, &mut state, cell);
+        }
+
+        state.result
+    };
+
+    #[cfg(feature = "parallel")]
+    let result = (0..args.board.cell_count())
+        .into_par_iter()
+        .fold(
+            || SearchState::new(args.board),
+            |mut state, cell| {
+                find_words_from(&args, &mut state, cell);
+                state
+            },
+        )
+        .map(|state| state.result)
+        .reduce(HashMap::new, |a, b| merge_results(&args, a, b));
+
+    // Convert hash map to vector
+    result
+        .into_iter()
+        .map(|(chosen, paths)| found_word(&args, &chosen, paths))
+        .collect()
+}
+
+/// Converts a word's letter elements and paths found in the search to a found word
+fn found_word(args: &SolverArgs, chosen: &[u8], mut paths: Vec<FoundPath>) -> FoundWord {
+    // Highest scoring path first
+    paths.sort_by(|a, b| a.order().cmp(&b.order()));
+
+    FoundWord {
+        word: chosen_string(args.dictionary, chosen),
+        paths: paths
+            .iter()
+            .map(|path| {
+                path.cells
+                    .iter()
+                    .map(|c| args.board.cell_coords(*c))
+                    .collect()
+            })
+            .collect(),
+        choices: paths
+            .iter()
+            .map(|path| {
+                path.choices
+                    .iter()
+                    .map(|&(step, start, len)| {
+                        (
+                            step,
+                            chosen_string(args.dictionary, &chosen[start..start + len]),
+                        )
+                    })
+                    .collect()
+            })
+            .collect(),
+        scores: paths.iter().filter_map(|path| path.score).collect(),
+    }
+}
+
+/// Find words in the dictionary on a list of boards.
+/// With the `parallel` feature enabled the boards are solved on the rayon thread pool
+pub fn find_words_batch(
+    boards: &[Board],
+    dictionary: &Dictionary,
+    all_paths: bool,
+) -> Vec<Vec<FoundWord>> {
+    let solve = |board| {
+        find_words(SolverArgs {
+            board,
+            dictionary,
+            all_paths,
+            debug: false,
+        })
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let result = boards.iter().map(solve).collect();
 
-    // Convert hash set to vector
-    result.into_iter().collect()
+    #[cfg(feature = "parallel")]
+    let result = boards.par_iter().map(solve).collect();
+
+    result
 }
 
-fn find_words_rec(
+/// Starts the search recursion at a cell
+fn find_words_from(args: &SolverArgs, state: &mut SearchState, cell: usize) {
+    if args.debug {
+        let (x, y) = args.board.cell_pos(cell);
+        eprintln!("Starting at {x}x{y}");
+    }
+
+    if !matches!(args.board.cell_face(cell), DiceFace::Stop) {
+        find_words_rec(args, state, cell, 0);
+    }
+}
+
+/// Merges two search results
+#[cfg(feature = "parallel")]
+fn merge_results(
     args: &SolverArgs,
-    chosen: &mut Vec<u8>,
-    visited: &mut HashSet<(usize, usize)>,
-    x: usize,
-    y: usize,
-    mut dict_elem: u32,
-    result: &mut HashSet<String>,
-) {
-    // Add to visited
-    visited.insert((x, y));
-
-    // Save chosen length
-    let in_chosen = chosen.len();
-
-    // Loop each face element
-    for l in args.board.dict_ents(x, y) {
-        dict_elem = args.dictionary.lookup_elem_letter_num(dict_elem, *l);
-
-        if dict_elem == 0 {
-            // No word found
-            break;
+    mut a: HashMap<Vec<u8>, Vec<FoundPath>>,
+    b: HashMap<Vec<u8>, Vec<FoundPath>>,
+) -> HashMap<Vec<u8>, Vec<FoundPath>> {
+    for (chosen, paths) in b {
+        match a.get_mut(&chosen) {
+            Some(existing) => {
+                if args.all_paths {
+                    existing.extend(paths);
+                } else if paths[0].order() < existing[0].order() {
+                    *existing = paths;
+                }
+            }
+            None => {
+                a.insert(chosen, paths);
+            }
+        }
+    }
+
+    a
+}
+
+fn find_words_rec(args: &SolverArgs, state: &mut SearchState, cell: usize, dict_elem: u32) {
+    // Add to visited and path
+    state.visited.insert(cell);
+    state.path.push(cell);
+
+    // Save chosen length and path step
+    let in_chosen = state.chosen.len();
+    state.starts.push(in_chosen);
+    let step = state.path.len() - 1;
+
+    face_options(args, cell, |letters, choice| {
+        // Follow the letters down the dictionary tree
+        let mut next_elem = dict_elem;
+
+        for l in letters {
+            next_elem = args.dictionary.lookup_elem_letter_num(next_elem, *l);
+
+            if next_elem == 0 {
+                // No word found
+                return;
+            }
         }
 
         // Add to chosen letters
-        chosen.push(*l);
+        state.chosen.extend_from_slice(letters);
+
+        if choice {
+            state.choices.push((step, in_chosen, letters.len()));
+        }
 
         if args.debug {
-            debug_lookup(chosen, dict_elem);
+            debug_lookup(args.dictionary, &state.chosen, next_elem);
         }
-    }
 
-    if dict_elem != 0 {
-        if args.dictionary.elem_ends_word(dict_elem) {
-            // Found word end - add to results
-            result.insert(chosen_string(chosen));
+        find_words_next(args, state, cell, next_elem);
+
+        if choice {
+            state.choices.pop();
+        }
+
+        state.chosen.truncate(in_chosen);
+    });
+
+    // Remove from visited and path
+    state.visited.remove(cell);
+    state.path.pop();
+    state.starts.pop();
+}
+
+/// Calls a function with each run of letters a cell's face can stand for and whether it is a
+/// choice: every letter of the alphabet for a wildcard face, each reading of an alternatives
+/// face, or the letters of any other face. Both [`find_words`] and [`find_word`] search with
+/// this so they follow the same rules
+fn face_options(args: &SolverArgs, cell: usize, mut f: impl FnMut(&[u8], bool)) {
+    match args.board.cell_face(cell) {
+        DiceFace::Wildcard => {
+            for l in 1..=args.dictionary.alphabet().len() as u8 {
+                f(&[l], true);
+            }
+        }
+        DiceFace::Alternatives(_) => {
+            for reading in args.board.cell_readings(cell) {
+                f(reading, true);
+            }
         }
+        _ => f(args.board.cell_dict_ents(cell), false),
+    }
+}
+
+/// Records a word ending at a cell and continues the search with the cell's neighbours
+fn find_words_next(args: &SolverArgs, state: &mut SearchState, cell: usize, dict_elem: u32) {
+    if args.dictionary.elem_ends_word(dict_elem) {
+        // Found word end - add to results
+        let score = path_score(args.board, &state.path, &state.starts, &state.chosen);
 
-        // Recurse neighbours
-        for (x, y) in args.board.neighbours(x, y) {
-            // Don't process visited dice or stop face
-            if !visited.contains(&(x, y)) && !matches!(args.board.face(x, y), DiceFace::Stop) {
-                find_words_rec(args, chosen, visited, x, y, dict_elem, result);
+        let found_path = || FoundPath {
+            cells: state.path.clone(),
+            choices: state.choices.clone(),
+            score,
+        };
+
+        match state.result.get_mut(state.chosen.as_slice()) {
+            Some(paths) => {
+                if args.all_paths {
+                    paths.push(found_path());
+                } else if path_order(score, &state.path, &state.choices) < paths[0].order() {
+                    // Keep the highest scoring path
+                    paths[0] = found_path();
+                }
+            }
+            None => {
+                state
+                    .result
+                    .insert(state.chosen.clone(), vec![found_path()]);
             }
         }
     }
 
-    // Remove from visited
-    visited.remove(&(x, y));
+    // Recurse neighbours
+    for &next in args.board.cell_neighbours(cell) {
+        // Don't process visited dice or stop face
+        if !state.visited.contains(next) && !matches!(args.board.cell_face(next), DiceFace::Stop) {
+            find_words_rec(args, state, next, dict_elem);
+        }
+    }
+}
+
+/// Bit set of board cells
+struct CellSet {
+    bits: Vec<u64>,
+}
 
-    // SAFETY: length always decreasing and always removing the pushed entry/entries above
-    unsafe {
-        chosen.set_len(in_chosen);
+impl CellSet {
+    /// Creates an empty cell set for a number of cells
+    fn new(cells: usize) -> Self {
+        Self {
+            bits: vec![0; cells.div_ceil(64)],
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, cell: usize) {
+        self.bits[cell >> 6] |= 1 << (cell & 63);
+    }
+
+    #[inline]
+    fn remove(&mut self, cell: usize) {
+        self.bits[cell >> 6] &= !(1 << (cell & 63));
+    }
+
+    #[inline]
+    fn contains(&self, cell: usize) -> bool {
+        self.bits[cell >> 6] & (1 << (cell & 63)) != 0
     }
 }
 
 /// Converts chosen element vector to a string
 #[inline]
-fn chosen_string(chosen: &[u8]) -> String {
+fn chosen_string(dictionary: &Dictionary, chosen: &[u8]) -> String {
+    let alphabet = dictionary.alphabet();
+
     chosen
         .iter()
-        .map(|e| Dictionary::elem_to_uchar(*e))
+        .map(|e| alphabet.elem_to_uchar(*e))
         .collect::<String>()
 }
 
 /// Debug output for dictionary lookups
 #[cold]
-fn debug_lookup(chosen: &[u8], dict_elem: u32) {
-    let string = chosen_string(chosen);
-    let indent = string.len() - 1;
+fn debug_lookup(dictionary: &Dictionary, chosen: &[u8], dict_elem: u32) {
+    let string = chosen_string(dictionary, chosen);
+    let indent = chosen.len() - 1;
+
+    eprintln!("{:indent$}{} ({:?})", "", string, dict_elem);
+}
+
+#[cfg(test)]
+mod tests {
+    use board::{CellBonus, GameType, LetterValues, Topology};
+    use dictionary::Alphabet;
+
+    use super::*;
+    use crate::test_helpers::{make_board, make_dictionary, solve, solve_board};
+
+    #[test]
+    fn paths() {
+        // R U
+        // S T
+        let result = solve(&["R", "U", "S", "T"], "rust\nrut\nnot", false);
+
+        assert_eq!(result.len(), 2);
+
+        assert_eq!(result[0].word, "RUST");
+        assert_eq!(
+            result[0].paths,
+            vec![vec![(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)]]
+        );
+
+        assert_eq!(result[1].word, "RUT");
+        assert_eq!(result[1].paths, vec![vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]]);
+    }
+
+    #[test]
+    fn all_paths() {
+        // A B
+        // A .
+        let result = solve(&["A", "B", "A", ""], "ab\nbaa", false);
+
+        // The smallest cell sequence is kept whatever order the cells are searched in
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].paths, vec![vec![(0, 0, 0), (1, 0, 0)]]);
+        assert_eq!(result[1].paths, vec![vec![(1, 0, 0), (0, 0, 0), (0, 1, 0)]]);
+
+        let mut result = solve(&["A", "B", "A", ""], "ab\nbaa", true);
+
+        assert_eq!(result[0].word, "AB");
+        result[0].paths.sort();
+        assert_eq!(
+            result[0].paths,
+            vec![vec![(0, 0, 0), (1, 0, 0)], vec![(0, 1, 0), (1, 0, 0)]]
+        );
+
+        assert_eq!(result[1].word, "BAA");
+        result[1].paths.sort();
+        assert_eq!(
+            result[1].paths,
+            vec![
+                vec![(1, 0, 0), (0, 0, 0), (0, 1, 0)],
+                vec![(1, 0, 0), (0, 1, 0), (0, 0, 0)]
+            ]
+        );
+    }
+
+    #[test]
+    fn toroidal() {
+        // C A T
+        // . . .
+        // . . .
+        let board = make_board(&["C", "A", "T", "", "", "", "", "", ""]);
+        let dictionary = make_dictionary("act\ncat");
+
+        let result = solve_board(&board, &dictionary, false);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].word, "CAT");
+
+        // C and T are adjacent when the board wraps around
+        let board = board.with_topology(Topology::Toroidal);
+        let result = solve_board(&board, &dictionary, false);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].word, "ACT");
+        assert_eq!(result[0].paths, vec![vec![(1, 0, 0), (0, 0, 0), (2, 0, 0)]]);
+    }
+
+    #[test]
+    fn hex() {
+        //  C A
+        //   . T
+        let board = make_board(&["C", "A", "", "T"]).with_topology(Topology::Hex);
+
+        let result = solve_board(&board, &make_dictionary("cat\nact\ntac"), false);
+
+        // C and T are diagonal on a square grid but not adjacent on a hex grid
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].word, "CAT");
+        assert_eq!(result[0].paths, vec![vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]]);
+        assert_eq!(result[1].word, "TAC");
+    }
+
+    #[test]
+    fn graph() {
+        // Ring of cells R-I-N-G-S linked back to R
+        let board = Board::from_layout_json(
+            r#"{
+                "cells": [
+                    { "x": 0, "y": 0, "face": "R" },
+                    { "x": 1, "y": 0, "face": "I" },
+                    { "x": 2, "y": 0, "face": "N" },
+                    { "x": 2, "y": 1, "face": "G" },
+                    { "x": 1, "y": 1, "face": "S" }
+                ],
+                "links": [[0, 1], [1, 2], [2, 3], [3, 4], [4, 0]]
+            }"#,
+            &Alphabet::english(),
+        )
+        .unwrap();
+
+        let result = solve_board(&board, &make_dictionary("ring\nrings\nsir\nsign"), false);
+
+        // S and I are not linked
+        assert_eq!(
+            result.iter().map(|f| f.word.as_str()).collect::<Vec<_>>(),
+            vec!["RING", "RINGS"]
+        );
+        assert_eq!(
+            result[1].paths,
+            vec![vec![(0, 0, 0), (1, 0, 0), (2, 0, 0), (2, 1, 0), (1, 1, 0)]]
+        );
+    }
+
+    #[test]
+    fn cube() {
+        // Layer 1: C A   Layer 2: . .
+        //          . .            . T
+        let board = Board::new_with_depth(
+            None,
+            None,
+            2,
+            ["C", "A", "", "", "", "", "", "T"]
+                .map(String::from)
+                .to_vec(),
+            &Alphabet::english(),
+        )
+        .unwrap();
+
+        let result = solve_board(&board, &make_dictionary("cat"), false);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].paths, vec![vec![(0, 0, 0), (1, 0, 0), (1, 1, 1)]]);
+    }
+
+    #[test]
+    fn wildcard() {
+        // C ?
+        // . T
+        let result = solve(&["C", "?", "", "T"], "cat\ncot\ncut\nact\nto\ncab", false);
+
+        assert_eq!(
+            result.iter().map(|f| f.word.as_str()).collect::<Vec<_>>(),
+            vec!["ACT", "CAT", "COT", "CUT", "TO"]
+        );
+
+        // Wildcard at the start of the word
+        assert_eq!(result[0].paths, vec![vec![(1, 0, 0), (0, 0, 0), (1, 1, 0)]]);
+        assert_eq!(result[0].choices, vec![vec![(0, "A".to_string())]]);
+
+        // Wildcard in the middle of the word
+        for f in &result[1..4] {
+            let letter = f.word.chars().nth(1).unwrap();
+
+            assert_eq!(f.paths, vec![vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]]);
+            assert_eq!(f.choices, vec![vec![(1, letter.to_string())]]);
+        }
 
-    println!("{:indent$}{} ({:?})", "", string, dict_elem);
+        // Paths without the wildcard have no choices
+        let result = solve(&["?", "T", "O", "", "", "", "", "", ""], "to", true);
+
+        let mut paths = result[0]
+            .paths
+            .iter()
+            .zip(&result[0].choices)
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                (&vec![(1, 0, 0), (0, 0, 0)], &vec![(1, "O".to_string())]),
+                (&vec![(1, 0, 0), (2, 0, 0)], &vec![])
+            ]
+        );
+    }
+
+    #[test]
+    fn alternatives() {
+        // Q/QU A
+        // I    T
+        let result = solve(
+            &["Q/QU", "A", "I", "T"],
+            "qat\nqi\nquai\nquit\nquat\nait",
+            false,
+        );
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|f| (f.word.as_str(), f.choices[0].clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("AIT", vec![]),
+                ("QAT", vec![(0, "Q".to_string())]),
+                ("QI", vec![(0, "Q".to_string())]),
+                ("QUAI", vec![(0, "QU".to_string())]),
+                ("QUAT", vec![(0, "QU".to_string())]),
+                ("QUIT", vec![(0, "QU".to_string())]),
+            ]
+        );
+
+        // Readings can be in the middle of a word and of different lengths
+        let result = solve(&["S", "IN/I", "G", ""], "sing\nsig\nsin", false);
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|f| (f.word.as_str(), f.choices[0].clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("SIG", vec![(1, "I".to_string())]),
+                ("SIN", vec![(1, "IN".to_string())]),
+                ("SING", vec![(1, "IN".to_string())]),
+            ]
+        );
+
+        // The QU faces of the built in game types are read as Q or QU
+        let board = Board::new_random_seeded(GameType::Classic, 55);
+
+        let result = solve_board(&board, &make_dictionary("qats\nquest"), false);
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|f| (f.word.as_str(), f.choices[0].clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("QATS", vec![(0, "Q".to_string())]),
+                ("QUEST", vec![(0, "QU".to_string())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn scoring() {
+        // C A    with bonuses  .  .
+        // A T                  TL DW
+        let board = make_board(&["C", "A", "A", "T"])
+            .with_letter_values(LetterValues::from_string("C=3 A=1 T=1").unwrap())
+            .with_bonuses(CellBonus::from_list(". . TL DW").unwrap())
+            .unwrap();
+        let dictionary = make_dictionary("cat\nta");
+
+        // Only the highest scoring path is kept
+        let result = solve_board(&board, &dictionary, false);
+
+        assert_eq!(result[0].word, "CAT");
+        assert_eq!(result[0].paths, vec![vec![(0, 0, 0), (0, 1, 0), (1, 1, 0)]]);
+        assert_eq!(result[0].scores, vec![(3 + 3 + 1) * 2]);
+        assert_eq!(result[1].word, "TA");
+        assert_eq!(result[1].scores, vec![(1 + 3) * 2]);
+
+        // All paths are sorted highest score first
+        let result = solve_board(&board, &dictionary, true);
+
+        assert_eq!(
+            result[0].paths,
+            vec![
+                vec![(0, 0, 0), (0, 1, 0), (1, 1, 0)],
+                vec![(0, 0, 0), (1, 0, 0), (1, 1, 0)]
+            ]
+        );
+        assert_eq!(result[0].scores, vec![14, 10]);
+
+        let rules = ScoreRules::new(3);
+        assert_eq!(rules.found_score(&result[0]), 14);
+        assert_eq!(rules.found_score(&result[1]), 0);
+        assert_eq!(rules.total_score(&result), 14);
+
+        // Wildcards score nothing
+        let board = make_board(&["C", "?", "", "T"]).with_letter_values(LetterValues::scrabble());
+        let result = solve_board(&board, &dictionary, false);
+
+        assert_eq!(result[0].word, "CAT");
+        assert_eq!(result[0].scores, vec![3 + 1]);
+
+        // No scores without letter values
+        assert!(solve(&["C", "A", "A", "T"], "cat", false)[0]
+            .scores
+            .is_empty());
+    }
+
+    #[test]
+    fn batch() {
+        let boards = [
+            make_board(&["R", "U", "S", "T"]),
+            make_board(&["T", "S", "U", "R"]),
+        ];
+
+        let result = find_words_batch(&boards, &make_dictionary("rust\nrut"), false);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].len(), 2);
+        assert_eq!(result[1].len(), 2);
+    }
+
+    #[test]
+    fn unicode() {
+        // Ö L
+        // Ä B
+        let alphabet = Alphabet::named("german").unwrap();
+
+        let board = Board::new_with_alphabet(
+            None,
+            None,
+            ["ö", "l", "ä", "b"].iter().map(|f| f.to_string()).collect(),
+            &alphabet,
+        )
+        .unwrap();
+
+        let dictionary = Dictionary::new_from_string_with_alphabet(
+            "öl\nbäl\nlöb\nab",
+            alphabet,
+            Default::default(),
+            false,
+        )
+        .unwrap();
+
+        let result = solve_board(&board, &dictionary, false);
+
+        let words = result.iter().map(|f| f.word.as_str()).collect::<Vec<_>>();
+        assert_eq!(words, vec!["BÄL", "LÖB", "ÖL"]);
+    }
+
+    #[test]
+    fn alphabet_mismatch() {
+        // A B
+        // C Z
+        let board = make_board(&["A", "B", "C", "Z"]);
+        let dictionary = Dictionary::new_from_string_with_alphabet(
+            "ab\nba",
+            Alphabet::new("BA").unwrap(),
+            Default::default(),
+            false,
+        )
+        .unwrap();
+
+        let solve = |board| {
+            solve_board(board, &dictionary, false)
+                .into_iter()
+                .map(|f| f.word)
+                .collect::<Vec<_>>()
+        };
+
+        // An unconverted board is read with the wrong letters but doesn't panic, letters
+        // beyond the dictionary's alphabet are never found
+        assert_eq!(solve(&board).len(), 2);
+
+        // Converting the board checks its letters
+        assert!(board.clone().with_alphabet(dictionary.alphabet()).is_err());
+
+        let board = make_board(&["A", "B", "A", "A"])
+            .with_alphabet(dictionary.alphabet())
+            .unwrap();
+
+        assert_eq!(solve(&board), vec!["AB", "BA"]);
+    }
 }
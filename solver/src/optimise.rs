@@ -0,0 +1,496 @@
+use std::sync::Arc;
+
+use board::{Board, Dice, DiceFace, GameType};
+use dictionary::{Alphabet, Dictionary};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{find_words, ScoreRules, SolverArgs};
+
+/// Value the optimiser maximises
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Objective {
+    /// Total score of the words on the board
+    #[default]
+    Score,
+    /// Number of words on the board
+    Words,
+}
+
+impl Objective {
+    /// Returns the name of the objective
+    pub fn name(&self) -> &'static str {
+        match self {
+            Objective::Score => "score",
+            Objective::Words => "words",
+        }
+    }
+}
+
+/// Settings for a new optimiser run
+#[derive(Clone, Debug)]
+pub struct OptimiseConfig {
+    /// Value to maximise
+    pub objective: Objective,
+    /// Only use the faces of the game type's dice. Otherwise any letter of the alphabet can be
+    /// on any cell
+    pub restrict_dice: bool,
+    /// Starting temperature. Higher temperatures accept more changes which make the board
+    /// worse, 0 only accepts changes which make the board no worse (hill climbing)
+    pub temperature: f64,
+    /// Factor the temperature is multiplied by after each iteration
+    pub cooling: f64,
+    /// Random number generator seed
+    pub seed: u64,
+    /// Description of the changes `prepare` makes to each board, such as the topology and
+    /// letter values. A resumed run must be given the same description
+    pub options: String,
+}
+
+impl Default for OptimiseConfig {
+    fn default() -> Self {
+        Self {
+            objective: Objective::default(),
+            restrict_dice: true,
+            temperature: 5.0,
+            cooling: 0.9995,
+            seed: 0,
+            options: String::new(),
+        }
+    }
+}
+
+/// Dice on the board as an order of all of the dice, the first of which are placed on the
+/// board cells, and the face showing on each dice
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Arrangement {
+    dice: Vec<usize>,
+    faces: Vec<usize>,
+}
+
+/// Optimiser state which is saved to resume a run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OptimiseState {
+    game: String,
+    dictionary: String,
+    options: String,
+    objective: Objective,
+    restrict_dice: bool,
+    cooling: f64,
+    seed: u64,
+    iteration: u64,
+    temperature: f64,
+    current: Arrangement,
+    current_value: u32,
+    best: Arrangement,
+    best_value: u32,
+}
+
+/// Simulated annealing search for the board with the highest score or most words. Each
+/// iteration either swaps two dice or turns a dice to another face, keeping the change if it
+/// improves the board or, with a probability falling with the temperature, if it doesn't
+pub struct Optimiser<'a> {
+    dictionary: &'a Dictionary,
+    prepare: Option<&'a dyn Fn(Board) -> Result<Board, String>>,
+    size: (usize, usize, usize),
+    dice: Vec<Dice>,
+    dice_alphabet: Arc<Alphabet>,
+    state: OptimiseState,
+}
+
+impl<'a> Optimiser<'a> {
+    /// Starts a new optimiser run for a game type from a random board. `prepare` makes changes
+    /// to each board before it is solved, for example setting the topology or letter values
+    pub fn new(
+        game_type: &GameType,
+        dictionary: &'a Dictionary,
+        prepare: Option<&'a dyn Fn(Board) -> Result<Board, String>>,
+        config: &OptimiseConfig,
+    ) -> Result<Self, String> {
+        let (dice, dice_alphabet) = Self::game_dice(game_type, dictionary, config.restrict_dice);
+
+        // Roll a random starting board
+        let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+
+        let mut order = (0..dice.len()).collect::<Vec<_>>();
+        order.shuffle(&mut rng);
+
+        let faces = dice
+            .iter()
+            .map(|d| rng.gen_range(0..d.face_count()))
+            .collect();
+
+        let start = Arrangement { dice: order, faces };
+
+        let mut optimiser = Self {
+            dictionary,
+            prepare,
+            size: Self::game_size(game_type),
+            dice,
+            dice_alphabet,
+            state: OptimiseState {
+                game: game_type.name().to_string(),
+                dictionary: Self::dictionary_description(dictionary),
+                options: config.options.clone(),
+                objective: config.objective,
+                restrict_dice: config.restrict_dice,
+                cooling: config.cooling,
+                seed: config.seed,
+                iteration: 0,
+                temperature: config.temperature,
+                current: start.clone(),
+                current_value: 0,
+                best: start,
+                best_value: 0,
+            },
+        };
+
+        let value = optimiser.evaluate(&optimiser.state.current)?;
+        optimiser.state.current_value = value;
+        optimiser.state.best_value = value;
+
+        Ok(optimiser)
+    }
+
+    /// Resumes an optimiser run from state saved with [`Optimiser::to_json`]. The dictionary
+    /// and the description of the board options must match the ones the run was started with,
+    /// otherwise the best value found so far would have been scored by different rules
+    pub fn from_json(
+        string: &str,
+        game_type: &GameType,
+        dictionary: &'a Dictionary,
+        prepare: Option<&'a dyn Fn(Board) -> Result<Board, String>>,
+        options: &str,
+    ) -> Result<Self, String> {
+        let state: OptimiseState = serde_json::from_str(string).map_err(|e| e.to_string())?;
+
+        if state.game != game_type.name() {
+            Err(format!(
+                "Optimiser state is for the {} game type, not {}",
+                state.game,
+                game_type.name()
+            ))?
+        }
+
+        let description = Self::dictionary_description(dictionary);
+
+        if state.dictionary != description {
+            Err(format!(
+                "Optimiser state is for a dictionary of {}, not {description}",
+                state.dictionary
+            ))?
+        }
+
+        if state.options != options {
+            Err(format!(
+                "Optimiser state is for board options '{}', not '{options}'",
+                state.options
+            ))?
+        }
+
+        let (dice, dice_alphabet) = Self::game_dice(game_type, dictionary, state.restrict_dice);
+
+        // Check the arrangements match the dice
+        for arrangement in [&state.current, &state.best] {
+            let mut order = arrangement.dice.clone();
+            order.sort_unstable();
+
+            if !order.iter().copied().eq(0..dice.len())
+                || arrangement.faces.len() != dice.len()
+                || arrangement
+                    .faces
+                    .iter()
+                    .zip(&dice)
+                    .any(|(face, d)| *face >= d.face_count())
+            {
+                Err("Optimiser state doesn't match the game type's dice")?
+            }
+        }
+
+        Ok(Self {
+            dictionary,
+            prepare,
+            size: Self::game_size(game_type),
+            dice,
+            dice_alphabet,
+            state,
+        })
+    }
+
+    /// Saves the optimiser state as a JSON document
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.state).expect("Optimiser state should serialise")
+    }
+
+    /// Runs one iteration, returning true if a new best board was found
+    pub fn step(&mut self) -> Result<bool, String> {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.state.seed.wrapping_add(self.state.iteration));
+
+        let cells = self.size.0 * self.size.1 * self.size.2;
+        let mut candidate = self.state.current.clone();
+
+        // Without the game type's dice every dice is the same so only faces are turned
+        if self.state.restrict_dice && self.dice.len() > 1 && rng.gen_bool(0.5) {
+            // Swap a dice on the board with any other dice
+            let a = rng.gen_range(0..cells);
+            let b = (a + rng.gen_range(1..self.dice.len())) % self.dice.len();
+
+            candidate.dice.swap(a, b);
+        } else {
+            // Turn a dice on the board to another face
+            let dice = candidate.dice[rng.gen_range(0..cells)];
+            let face_count = self.dice[dice].face_count();
+
+            if face_count > 1 {
+                candidate.faces[dice] =
+                    (candidate.faces[dice] + rng.gen_range(1..face_count)) % face_count;
+            }
+        }
+
+        let value = self.evaluate(&candidate)?;
+        let current = self.state.current_value;
+
+        let accept = value >= current
+            || (self.state.temperature > 0.0
+                && rng.gen::<f64>()
+                    < ((value as f64 - current as f64) / self.state.temperature).exp());
+
+        self.state.iteration += 1;
+        self.state.temperature *= self.state.cooling;
+
+        let improved = value > self.state.best_value;
+
+        if improved {
+            self.state.best = candidate.clone();
+            self.state.best_value = value;
+        }
+
+        if accept {
+            self.state.current = candidate;
+            self.state.current_value = value;
+        }
+
+        Ok(improved)
+    }
+
+    /// Returns the value being maximised
+    pub fn objective(&self) -> Objective {
+        self.state.objective
+    }
+
+    /// Returns the number of iterations run
+    pub fn iteration(&self) -> u64 {
+        self.state.iteration
+    }
+
+    /// Returns the current temperature
+    pub fn temperature(&self) -> f64 {
+        self.state.temperature
+    }
+
+    /// Returns the score or word count of the best board found
+    pub fn best_value(&self) -> u32 {
+        self.state.best_value
+    }
+
+    /// Returns the best board found
+    pub fn best_board(&self) -> Board {
+        self.board(&self.state.best)
+            .expect("Best board should have been built before")
+    }
+
+    /// Returns the dice for a game type, or a dice with every letter of the dictionary's
+    /// alphabet for each cell if not restricted to the game type's dice
+    fn game_dice(
+        game_type: &GameType,
+        dictionary: &Dictionary,
+        restrict_dice: bool,
+    ) -> (Vec<Dice>, Arc<Alphabet>) {
+        if restrict_dice {
+            (game_type.dice(), game_type.alphabet())
+        } else {
+            let (x, y, z) = Self::game_size(game_type);
+            let alphabet = dictionary.alphabet();
+
+            let dice = Dice::new(
+                alphabet
+                    .letters()
+                    .iter()
+                    .map(|c| DiceFace::Letter(*c))
+                    .collect(),
+            )
+            .expect("Alphabet should have letters");
+
+            (vec![dice; x * y * z], Arc::new(alphabet.clone()))
+        }
+    }
+
+    /// Describes a dictionary by its word count, word sizes, alphabet and the checksum of its
+    /// words, so a different word list of the same size doesn't match
+    fn dictionary_description(dictionary: &Dictionary) -> String {
+        format!(
+            "{} words (word sizes {}, alphabet {}, checksum {:08x})",
+            dictionary.word_count(),
+            dictionary.size_constraint(),
+            dictionary.alphabet(),
+            dictionary.checksum()
+        )
+    }
+
+    /// Returns the board dimensions for a game type
+    fn game_size(game_type: &GameType) -> (usize, usize, usize) {
+        let (x, y) = game_type.layout();
+        (x, y, game_type.depth())
+    }
+
+    /// Builds the board for an arrangement of the dice
+    fn board(&self, arrangement: &Arrangement) -> Result<Board, String> {
+        let (x, y, z) = self.size;
+
+        let faces = arrangement.dice[..x * y * z]
+            .iter()
+            .map(|&d| self.dice[d].face(arrangement.faces[d]))
+            .collect();
+
+        let mut board = Board::new_from_faces(x, y, z, faces, &self.dice_alphabet)?
+            .with_alphabet(self.dictionary.alphabet())?;
+
+        if let Some(prepare) = self.prepare {
+            board = prepare(board)?;
+        }
+
+        Ok(board)
+    }
+
+    /// Solves the board for an arrangement of the dice, returning the value being maximised
+    fn evaluate(&self, arrangement: &Arrangement) -> Result<u32, String> {
+        let board = self.board(arrangement)?;
+
+        let found = find_words(SolverArgs {
+            board: &board,
+            dictionary: self.dictionary,
+            all_paths: false,
+            debug: false,
+        });
+
+        Ok(match self.state.objective {
+            Objective::Score => ScoreRules::for_board(&board).total_score(&found),
+            Objective::Words => found.len() as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{make_dictionary, solve_board};
+
+    fn dictionary() -> Dictionary {
+        make_dictionary("cat\nact\ntac\ncast\nscat\nrest\nstar\nrats\ntars\narts")
+    }
+
+    #[test]
+    fn optimise() {
+        let dictionary = dictionary();
+        let game_type = GameType::from_name("classic").unwrap();
+
+        let mut optimiser = Optimiser::new(
+            &game_type,
+            &dictionary,
+            None,
+            &OptimiseConfig {
+                objective: Objective::Words,
+                seed: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let start = optimiser.best_value();
+
+        for _ in 0..200 {
+            optimiser.step().unwrap();
+        }
+
+        assert_eq!(optimiser.iteration(), 200);
+        assert!(optimiser.best_value() >= start);
+
+        // Best value is the value of the best board
+        let board = optimiser.best_board();
+        let found = solve_board(&board, &dictionary, false);
+        assert_eq!(found.len() as u32, optimiser.best_value());
+    }
+
+    #[test]
+    fn resume() {
+        let dictionary = dictionary();
+        let game_type = GameType::from_name("classic").unwrap();
+        let config = OptimiseConfig {
+            seed: 2,
+            restrict_dice: false,
+            ..Default::default()
+        };
+
+        // Run 20 iterations in one go
+        let mut optimiser = Optimiser::new(&game_type, &dictionary, None, &config).unwrap();
+
+        for _ in 0..20 {
+            optimiser.step().unwrap();
+        }
+
+        // Run 10 iterations, save, resume and run 10 more
+        let mut resumed = Optimiser::new(&game_type, &dictionary, None, &config).unwrap();
+
+        for _ in 0..10 {
+            resumed.step().unwrap();
+        }
+
+        let json = resumed.to_json();
+        let mut resumed = Optimiser::from_json(&json, &game_type, &dictionary, None, "").unwrap();
+
+        for _ in 0..10 {
+            resumed.step().unwrap();
+        }
+
+        assert_eq!(resumed.iteration(), 20);
+        assert_eq!(resumed.to_json(), optimiser.to_json());
+
+        // State must match the game type
+        let big = GameType::from_name("big-original").unwrap();
+        let err = Optimiser::from_json(&json, &big, &dictionary, None, "")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            "Optimiser state is for the classic game type, not big-original"
+        );
+
+        // State must match the dictionary and board options
+        let other = make_dictionary("cat\nact");
+        let err = Optimiser::from_json(&json, &game_type, &other, None, "")
+            .err()
+            .unwrap();
+        assert!(err.starts_with("Optimiser state is for a dictionary of 10 words"));
+
+        // A different word list of the same size doesn't match
+        let other = make_dictionary("cat\nact\ntac\ncast\nscat\nrest\nstar\nrats\ntars\nsort");
+        assert_eq!(other.word_count(), dictionary.word_count());
+
+        let err = Optimiser::from_json(&json, &game_type, &other, None, "")
+            .err()
+            .unwrap();
+        assert!(err.starts_with("Optimiser state is for a dictionary of 10 words"));
+
+        let err = Optimiser::from_json(&json, &game_type, &dictionary, None, "topology=torus")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            "Optimiser state is for board options '', not 'topology=torus'"
+        );
+    }
+}
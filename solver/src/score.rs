@@ -0,0 +1,123 @@
+use board::{Board, DiceFace};
+
+use crate::FoundWord;
+
+/// Official Boggle scoring rules
+pub struct ScoreRules {
+    min_len: usize,
+}
+
+impl ScoreRules {
+    /// Creates scoring rules with a given minimum word length
+    pub fn new(min_len: usize) -> Self {
+        Self { min_len }
+    }
+
+    /// Returns the official scoring rules for a board. Boards with up to 16 dice use the
+    /// classic 3 letter minimum, larger boards use the Big Boggle 4 letter minimum
+    pub fn for_board(board: &Board) -> Self {
+        Self::new(if board.cell_count() > 16 { 4 } else { 3 })
+    }
+
+    /// Returns the minimum word length which scores
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Returns the score for a word. Letters are counted individually so a ligature
+    /// dice face such as QU counts as two letters
+    pub fn word_score(&self, word: &str) -> u32 {
+        let len = word.chars().count();
+
+        if len < self.min_len {
+            return 0;
+        }
+
+        match len {
+            0..=4 => 1,
+            5 => 2,
+            6 => 3,
+            7 => 5,
+            _ => 11,
+        }
+    }
+
+    /// Returns the score for a found word. Words found on a board with letter values score
+    /// their best path, otherwise the word scores by length
+    pub fn found_score(&self, found: &FoundWord) -> u32 {
+        match found.scores.first() {
+            Some(score) if found.word.chars().count() >= self.min_len => *score,
+            Some(_) => 0,
+            None => self.word_score(&found.word),
+        }
+    }
+
+    /// Returns the total score for a list of found words
+    pub fn total_score(&self, words: &[FoundWord]) -> u32 {
+        words.iter().map(|w| self.found_score(w)).sum()
+    }
+}
+
+/// Returns the Ruzzle and Wordament style score of a path through the board, or None if the
+/// board has no letter values. Each cell's letters score their value multiplied by any letter
+/// bonus on the cell, and the total is multiplied by the word bonuses of every cell on the
+/// path. `starts` holds the start of each cell's letters in `chosen`. Wildcards score nothing
+pub(crate) fn path_score(
+    board: &Board,
+    cells: &[usize],
+    starts: &[usize],
+    chosen: &[u8],
+) -> Option<u32> {
+    board.letter_values()?;
+
+    let mut total = 0;
+    let mut word_multiplier = 1;
+
+    for (step, &cell) in cells.iter().enumerate() {
+        let bonus = board.cell_bonus(cell);
+
+        if !matches!(board.cell_face(cell), DiceFace::Wildcard) {
+            let end = starts.get(step + 1).copied().unwrap_or(chosen.len());
+
+            let letters = chosen[starts[step]..end]
+                .iter()
+                .map(|e| board.elem_value(*e))
+                .sum::<u32>();
+
+            total += letters * bonus.letter_multiplier();
+        }
+
+        word_multiplier *= bonus.word_multiplier();
+    }
+
+    Some(total * word_multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic() {
+        let rules = ScoreRules::new(3);
+
+        assert_eq!(rules.word_score("AT"), 0);
+        assert_eq!(rules.word_score("CAT"), 1);
+        assert_eq!(rules.word_score("CATS"), 1);
+        assert_eq!(rules.word_score("QUEEN"), 2);
+        assert_eq!(rules.word_score("QUEENS"), 3);
+        assert_eq!(rules.word_score("SQUEEZE"), 5);
+        assert_eq!(rules.word_score("QUANTALS"), 11);
+        assert_eq!(rules.word_score("QUADRATICS"), 11);
+    }
+
+    #[test]
+    fn big() {
+        let board = Board::new(None, None, vec!["A".to_string(); 25]).unwrap();
+        let rules = ScoreRules::for_board(&board);
+
+        assert_eq!(rules.min_len(), 4);
+        assert_eq!(rules.word_score("CAT"), 0);
+        assert_eq!(rules.word_score("CATS"), 1);
+    }
+}
@@ -0,0 +1,40 @@
+//! Helpers shared by the solver tests
+
+use board::Board;
+use dictionary::Dictionary;
+
+use crate::{find_words, FoundWord, SolverArgs};
+
+/// Creates a board from a list of dice faces
+pub(crate) fn make_board(faces: &[&str]) -> Board {
+    Board::new(None, None, faces.iter().map(|f| f.to_string()).collect()).unwrap()
+}
+
+/// Creates a dictionary from words separated by new lines
+pub(crate) fn make_dictionary(words: &str) -> Dictionary {
+    Dictionary::new_from_string(words, Default::default(), false).unwrap()
+}
+
+/// Finds the words on a board, sorted alphabetically
+pub(crate) fn solve_board(
+    board: &Board,
+    dictionary: &Dictionary,
+    all_paths: bool,
+) -> Vec<FoundWord> {
+    let mut result = find_words(SolverArgs {
+        board,
+        dictionary,
+        all_paths,
+        debug: false,
+    });
+
+    result.sort_by(|a, b| a.word.cmp(&b.word));
+
+    result
+}
+
+/// Finds the words from a list on a board made from a list of dice faces, sorted
+/// alphabetically
+pub(crate) fn solve(faces: &[&str], words: &str, all_paths: bool) -> Vec<FoundWord> {
+    solve_board(&make_board(faces), &make_dictionary(words), all_paths)
+}